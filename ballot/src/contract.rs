@@ -9,16 +9,33 @@ extern crate pbc_lib;
 mod zk_compute;
 
 use create_type_spec_derive::CreateTypeSpec;
-use pbc_contract_common::address::Address;
-use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::address::{Address, Shortname};
+use pbc_contract_common::context::{CallbackContext, ContractContext};
 use pbc_contract_common::events::EventGroup;
-use pbc_contract_common::sorted_vec_map::SortedVecMap;
+use pbc_contract_common::signature::Signature;
+use pbc_contract_common::sorted_vec_map::{SortedVecMap, SortedVecSet};
+use pbc_contract_common::upgrade::ContractHashes;
 use pbc_contract_common::zk::CalculationStatus;
-use pbc_contract_common::zk::{SecretVarId, ZkInputDef, ZkState, ZkStateChange};
-use pbc_traits::ReadWriteState;
-use pbc_zk::Sbi8;
+use pbc_contract_common::zk::{AttestationId, SecretVarId, ZkInputDef, ZkState, ZkStateChange};
+use pbc_contract_common::Hash;
+use pbc_traits::{ReadRPC, ReadWriteState, WriteRPC};
+use pbc_zk::{Sbi128, Sbi8};
 use read_write_rpc_derive::ReadWriteRPC;
 use read_write_state_derive::ReadWriteState;
+use sekiva_common::{fail, require, ContractError, ProcessCounter};
+
+/// Version of the `BallotState` layout, bumped whenever the layout changes
+const STATE_VERSION: u32 = 1;
+
+/// Shortname of the action receiving `BallotEvent`s, on the organization and on listeners
+const BALLOT_EVENT_SHORTNAME: Shortname = Shortname::from_u32(0x41);
+/// Shortname of `balance_at(owner, timestamp)` of the checkpoint token interface.
+///
+/// This is not part of MPC-20, token-gated ballots need a token implementing it, which the
+/// organization checks before deploying the ballot.
+const TOKEN_BALANCE_AT_SHORTNAME: Shortname = Shortname::from_u32(0x20);
+/// Prefix of attested results, separating them from other data signed by the ZK nodes
+const RESULT_ATTESTATION_DOMAIN: &[u8] = b"SEKIVA_BALLOT_RESULT_V1";
 
 /// Secret vote metadata
 #[derive(ReadWriteState, ReadWriteRPC, Debug)]
@@ -28,6 +45,26 @@ enum SecretVarType {
     Vote {},
     #[discriminant(1)]
     TallyResult {},
+    #[discriminant(2)]
+    ResultCopy {},
+    #[discriminant(3)]
+    AdministratorTokenShare { voter: Address },
+    #[discriminant(4)]
+    AnonymousVote {},
+    #[discriminant(5)]
+    DrawContribution {},
+    #[discriminant(6)]
+    DrawSeed {},
+    #[discriminant(7)]
+    BudgetAllocation {},
+    #[discriminant(8)]
+    BudgetResult {},
+    #[discriminant(9)]
+    PartialTally {},
+    #[discriminant(10)]
+    VoteCheck { first_vote_id: u32 },
+    #[discriminant(11)]
+    ApproverTokenShare { voter: Address },
 }
 
 #[derive(CreateTypeSpec, ReadWriteState, Debug, PartialEq, Clone, Copy, ReadWriteRPC)]
@@ -41,6 +78,10 @@ enum BallotStatus {
     Completed {},
     #[discriminant(3)]
     Cancelled {},
+    #[discriminant(4)]
+    TallyFailed {},
+    #[discriminant(5)]
+    Paused {},
 }
 
 #[derive(CreateTypeSpec, ReadWriteState, Debug, PartialEq, Clone, Copy)]
@@ -54,6 +95,94 @@ enum BallotProcessState {
     Completed {},
     #[discriminant(3)]
     Cancelled {},
+    #[discriminant(4)]
+    TallyFailed {},
+    #[discriminant(5)]
+    Paused {},
+}
+
+/// How a running ballot follows membership changes of its organization
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum MembershipPolicy {
+    /// Eligible voters are fixed at deployment
+    #[discriminant(0)]
+    Snapshot {},
+    /// New members become eligible, removed members stay eligible
+    #[discriminant(1)]
+    AddOnly {},
+    /// Eligible voters follow the organization, including `sync_voters`
+    #[discriminant(2)]
+    LiveSync {},
+}
+
+/// Who may vote on the ballot
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum Eligibility {
+    /// Members of the organization, proven against the voter snapshot
+    #[discriminant(0)]
+    Membership {},
+    /// Holders of at least `min_balance` of `token` when the ballot started
+    #[discriminant(1)]
+    TokenBalance { token: Address, min_balance: u128 },
+}
+
+/// What the ballot decides
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone)]
+#[repr(u8)]
+enum BallotMode {
+    /// Voters choose between the options
+    #[discriminant(0)]
+    Vote {},
+    /// `seats` members are drawn by lot from the voter snapshot
+    #[discriminant(1)]
+    Sortition { seats: u32 },
+    /// Voters allocate points to the options as projects, which are funded by points within
+    /// `budget`. `costs` holds the cost of each option
+    #[discriminant(2)]
+    Budgeting { budget: u32, costs: Vec<u32> },
+}
+
+/// What happens when no option of a completed ballot has a majority
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum RunoffRule {
+    /// The option with the most votes wins
+    #[discriminant(0)]
+    None {},
+    /// The organization deploys a runoff between the top two options, running for
+    /// `duration_seconds`
+    #[discriminant(1)]
+    TopTwo { duration_seconds: u64 },
+}
+
+/// Whether the addresses taking part in the ballot are public
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone, Copy)]
+#[repr(u8)]
+enum ParticipationMode {
+    /// Voters vote from their own address, which is recorded in `already_voted`
+    #[discriminant(0)]
+    Public {},
+    /// Every voter is issued a secret one-time token during the first `issuance_seconds` of the
+    /// ballot, and votes with it from any address once issuance has closed
+    #[discriminant(1)]
+    Anonymous { issuance_seconds: u64 },
+}
+
+/// Who gets to see the result of the ballot
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone)]
+#[repr(u8)]
+enum ResultDisclosure {
+    /// The result is opened on-chain for everyone
+    #[discriminant(0)]
+    Public {},
+    /// The result stays secret, eligible voters can request their own copy
+    #[discriminant(1)]
+    Voters {},
+    /// The result stays secret, the given organization administrators can request their own copy
+    #[discriminant(2)]
+    Administrators { administrators: Vec<Address> },
 }
 
 #[derive(CreateTypeSpec, ReadWriteState, Debug, PartialEq, Clone, Copy)]
@@ -65,6 +194,34 @@ enum ProcessState {
     Complete {},
     #[discriminant(2)]
     Ignored {},
+    #[discriminant(3)]
+    Failed {},
+}
+
+impl ProcessState {
+    /// Whether the process has ended, so its record may be pruned
+    fn is_finished(&self) -> bool {
+        !matches!(self, ProcessState::Received {})
+    }
+}
+
+/// Tracking record of a single process
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct ProcessRecord {
+    pub state: ProcessState,
+    pub timestamp: u64,
+    pub failure_reason: Option<String>, // Why the process failed or was ignored
+}
+
+/// Bounded log of processes.
+///
+/// Finished records are pruned once the log exceeds `MAX_TRACKED_PROCESSES` entries, or on
+/// request by age. Pruned records are only kept as counts.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct ProcessLog {
+    pub records: SortedVecMap<String, ProcessRecord>,
+    pub pruned_count: u64,    // Number of records pruned so far
+    pub pruned_failures: u64, // Number of pruned records that had a failure reason
 }
 
 #[derive(CreateTypeSpec, ReadWriteState, Clone)]
@@ -77,6 +234,27 @@ struct Tally {
     pub total: u32,
 }
 
+/// Opened result of a budgeting ballot, see `zk_compute::BudgetOutput`
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct BudgetTotals {
+    pub points_0: u32,
+    pub points_1: u32,
+    pub points_2: u32,
+    pub points_3: u32,
+    pub points_4: u32,
+    pub funded: u32,
+    pub spent: u32,
+}
+
+/// Outcome of a budgeting ballot
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct BudgetOutcome {
+    pub points: Vec<u32>,    // Points allocated to each project
+    pub funded: Vec<String>, // Funded projects, from most to fewest points
+    pub spent: u32,          // Sum of the costs of the funded projects
+    pub timestamp: u64,
+}
+
 #[derive(CreateTypeSpec, ReadWriteState, Clone)]
 struct TallyResult {
     pub option_0: u32,
@@ -86,6 +264,134 @@ struct TallyResult {
     pub option_4: u32,
 }
 
+/// An off-chain document belonging to the proposal, e.g. a PDF.
+///
+/// `sha256` is the lowercase hex encoded SHA-256 hash of the document, so readers can check
+/// the document they received is the one registered with the ballot.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+struct Attachment {
+    pub uri: String,
+    pub sha256: String,
+    pub mime_type: String,
+}
+
+/// A version of the ballot text as shown to voters.
+///
+/// The content hash is the SHA-256 hash of the RPC encoding of `(title, description, options)`.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct MetadataRevision {
+    pub title: String,
+    pub description: String,
+    pub options: Vec<String>,
+    pub content_hash: Hash,
+    pub timestamp: u64,
+}
+
+/// Public receipt allowing a voter to check that their vote was counted.
+///
+/// The commitment is the SHA-256 hash of the RPC encoding of
+/// `(ballot address, voter, variable_id, input_transaction)`, which the voter can recompute
+/// locally without revealing their choice.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct VoteReceipt {
+    pub input_transaction: Hash,
+    pub variable_id: Option<u32>, // Set once the input is confirmed by the ZK nodes
+    pub commitment: Option<Hash>, // Set once the input is confirmed by the ZK nodes
+    pub timestamp: u64,
+    pub metadata_revision: u32, // Index of the ballot text shown when the vote was cast
+}
+
+/// Record of a tally result that was kept secret.
+///
+/// The contract never sees the result, so `inputs_hash` does not commit to its value. It only
+/// binds the result variable to the ballot and the vote variables that were tallied:
+/// `SHA-256(rpc(ballot address, variable_id, tallied_variables))`.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct PrivateResult {
+    pub variable_id: u32,
+    pub inputs_hash: Hash,
+    pub timestamp: u64,
+}
+
+/// Record of a sortition draw, from which anyone can recompute the selection.
+///
+/// `selected_indices` are positions in the voter snapshot, derived from `seed` and
+/// `voter_count` by a partial Fisher-Yates shuffle of all positions: for every seat `i`,
+/// `j = i + r mod (voter_count - i)` is swapped into position `i`, where `r` is the first 8
+/// bytes, big-endian, of `SHA-256(rpc(ballot address, seed, i))`. The member at each drawn
+/// position is published with a membership proof for that position, see
+/// `publish_drawn_member`.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct DrawTranscript {
+    pub voter_root: Hash,               // Snapshot the members are drawn from
+    pub voter_count: u32,               // Members in the snapshot
+    pub contributions: Vec<u32>,        // Secret variables holding the contributed randomness
+    pub seed: u128,                     // Sum of all contributions, opened after the draw
+    pub selected_indices: Vec<u32>,     // Drawn positions in the snapshot, one per seat
+    pub selected: Vec<Option<Address>>, // Member at each drawn position, once published
+    pub timestamp: u64,
+}
+
+/// Addresses with a role on the ballot besides its administrator, who created it.
+///
+/// Empty `talliers` or `cancellers` leave tallying or cancelling to the administrator.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+struct BallotRoles {
+    pub talliers: Vec<Address>, // May start, fold, retry and abandon the tally
+    pub cancellers: Vec<Address>, // May propose a cancellation, confirmed by a second one
+    pub observers: Vec<Address>, // May request a copy of a private result
+}
+
+/// A cancellation waiting for confirmation by a second canceller or the administrator.
+///
+/// Times are block production times in milliseconds. An expired proposal can no longer be
+/// confirmed, and may be replaced by a new one.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct CancellationProposal {
+    pub proposer: Address,
+    pub proposed_at: u64,
+    pub expires_at: u64,
+}
+
+/// Who has to approve cancelling the ballot and syncing its voters.
+///
+/// `approvers` are the organization administrators when the ballot was deployed. With a
+/// `threshold` above 1, those actions only run once `threshold` approvers approved them
+/// within `window_seconds`.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+struct ApprovalPolicy {
+    pub approvers: Vec<Address>,
+    pub threshold: u32,
+    pub window_seconds: u64,
+}
+
+/// An action that needs approval under the `ApprovalPolicy` of the ballot
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Debug, PartialEq, Clone)]
+#[repr(u8)]
+enum BallotAction {
+    /// Cancels the ballot, see `cancel_ballot`
+    #[discriminant(0)]
+    Cancel {},
+    /// Replaces the voter snapshot, see `sync_voters`
+    #[discriminant(1)]
+    SyncVoters { voter_root: Hash, voter_count: u32 },
+}
+
+/// An action collecting approvals
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct PendingAction {
+    pub action: BallotAction,
+    pub approvals: Vec<Address>, // Approvers that approved the action so far
+    pub expires_at: u64,         // Block production time after which the approvals lapse
+}
+
+/// Signatures of the ZK nodes over the canonical encoding of the result
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct ResultAttestation {
+    pub data: Vec<u8>, // Canonical encoding, see `result_attestation_data`
+    pub signatures: Vec<Option<Signature>>, // One per ZK node, `None` if it did not sign
+}
+
 #[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
 #[repr(u8)]
 enum BallotEvent {
@@ -112,6 +418,33 @@ enum BallotEvent {
         timestamp: u64,
         process_id: String,
     },
+    #[discriminant(5)]
+    CommitteeDrawn {
+        selected: Vec<Address>,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(6)]
+    RunoffRequired {
+        options: Vec<String>,
+        voter_root: Hash,
+        voter_count: u32,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(7)]
+    BudgetAllocated {
+        funded: Vec<String>,
+        spent: u32,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(8)]
+    VoteRejected {
+        voter: Address,
+        timestamp: u64,
+        process_id: String,
+    },
 }
 
 #[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
@@ -152,6 +485,7 @@ enum OrganizationEvent {
 
 #[state]
 struct BallotState {
+    state_version: u32, // Layout version of this state, see `STATE_VERSION`
     organization: Address,
     administrator: Address,
     title: String,
@@ -161,25 +495,522 @@ struct BallotState {
     end_time: u64,
     status: Option<BallotStatus>,
     tally: Option<Tally>,
-    eligible_voters: Vec<Address>,
-    already_voted: Vec<Address>, // Track addresses that have already voted
+    voter_root: Hash, // Merkle root of the eligible voter snapshot
+    voter_count: u32, // Number of voters in the snapshot
+    added_voters: SortedVecSet<Address>, // Voters added after the snapshot was taken
+    removed_voters: SortedVecSet<Address>, // Voters removed after the snapshot was taken
+    membership_policy: MembershipPolicy, // How membership changes affect eligible voters
+    already_voted: SortedVecSet<Address>, // Track addresses that have already voted
     process_state: BallotProcessState, // Track the current process state of this ballot
-    process_id: String,          // Unique identifier for this ballot process
-    event_processes: SortedVecMap<String, ProcessState>, // Track processes from events
+    process_id: String, // Unique identifier for this ballot process
+    event_processes: ProcessLog, // Track processes from events
+    process_counter: ProcessCounter, // Allocates process IDs
+    tally_started_at: Option<u64>, // When the current tally attempt was started
+    tally_attempts: u32, // Number of times the tally computation has been started
+    failure_reason: Option<String>, // Why the last tally attempt failed, if it did
+    vote_receipts: SortedVecMap<Address, VoteReceipt>, // Public inclusion receipt per voter
+    tallied_variables: Vec<u32>, // Vote variables consumed by the tally computation
+    event_listener: Option<Address>, // Additional receiver of ballot events, e.g. an indexer
+    paused_at: Option<u64>, // When voting was paused, if it is
+    metadata_revisions: Vec<MetadataRevision>, // Every version of the ballot text, oldest first
+    attachments: Vec<Attachment>, // Off-chain proposal documents registered by content hash
+    eligibility: Eligibility, // Who may vote on the ballot
+    token_balances: SortedVecMap<Address, u128>, // Cached token balances at the ballot start
+    result_disclosure: ResultDisclosure, // Who gets to see the result
+    private_result: Option<PrivateResult>, // Set when a secret result was computed
+    result_recipients: SortedVecSet<Address>, // Who received a copy of the secret result
+    pending_recipient: Option<Address>, // Recipient of the copy being computed
+    result_attestation: Option<ResultAttestation>, // ZK node signatures over the public result
+    participation_mode: ParticipationMode, // Whether voting addresses are public
+    issuance_end: u64, // When anonymous token issuance closes and voting opens
+    administrator_shares: u32, // Token shares issued by the administrator
+    approver_shares: u32, // Token shares issued by the other approvers
+    anonymous_votes: u32, // Anonymous votes received and not rejected by their check
+    ballot_mode: BallotMode, // Whether the ballot is a vote, a sortition draw or a budget
+    draw_contributors: Vec<Address>, // Members that contributed randomness to the draw
+    draw_transcript: Option<DrawTranscript>, // Set once the committee is drawn
+    runoff_rule: RunoffRule, // Whether a result without majority triggers a runoff
+    parent_ballot: Option<Address>, // The ballot this ballot is a runoff of
+    budget_outcome: Option<BudgetOutcome>, // Set once the budget is allocated
+    partial_tally: Option<u32>, // Secret variable holding the running total of folded votes
+    folding_votes: Vec<u32>, // Votes being folded into the running total
+    folded_variables: Vec<u32>, // Vote variables folded into the running total so far
+    pending_checks: Vec<u32>, // Confirmed votes waiting for their range check
+    checking_votes: Vec<u32>, // Votes whose range check is running, in id order
+    check_started_at: Option<u64>, // When the running range check was started
+    rejected_voters: SortedVecSet<Address>, // Voters whose vote was out of range, may vote again
+    roles: BallotRoles, // Talliers, cancellers and observers of the ballot
+    cancellation: Option<CancellationProposal>, // Cancellation waiting for confirmation
+    approval_policy: ApprovalPolicy, // Who approves cancelling and syncing voters
+    pending_actions: Vec<PendingAction>, // Actions collecting approvals
+}
+
+/// Largest budget of a budgeting ballot, so sums of costs fit in the MPC computation
+const MAX_BUDGET: u32 = 1_000_000_000;
+
+/// Maximum number of attachments per ballot.
+const MAX_ATTACHMENTS: usize = 10;
+
+/// Maximum number of process records kept before the oldest finished ones are pruned.
+const MAX_TRACKED_PROCESSES: usize = 100;
+
+impl ProcessLog {
+    fn new() -> Self {
+        ProcessLog {
+            records: SortedVecMap::new(),
+            pruned_count: 0,
+            pruned_failures: 0,
+        }
+    }
+
+    /// Records the state of a process
+    fn record(&mut self, process_id: String, state: ProcessState, timestamp: u64) {
+        self.insert(
+            process_id,
+            ProcessRecord {
+                state,
+                timestamp,
+                failure_reason: None,
+            },
+        );
+    }
+
+    /// Records the state of a process together with why it failed or was ignored
+    fn record_failure(
+        &mut self,
+        process_id: String,
+        state: ProcessState,
+        timestamp: u64,
+        reason: String,
+    ) {
+        self.insert(
+            process_id,
+            ProcessRecord {
+                state,
+                timestamp,
+                failure_reason: Some(reason),
+            },
+        );
+    }
+
+    fn insert(&mut self, process_id: String, record: ProcessRecord) {
+        self.records.insert(process_id, record);
+        while self.records.len() > MAX_TRACKED_PROCESSES {
+            let oldest = self
+                .records
+                .iter()
+                .filter(|(_, record)| record.state.is_finished())
+                .min_by_key(|(_, record)| record.timestamp)
+                .map(|(process_id, _)| process_id.clone());
+            match oldest {
+                Some(process_id) => self.archive(process_id),
+                None => break,
+            }
+        }
+    }
+
+    /// Prunes all finished records older than the cutoff
+    fn prune_older_than(&mut self, cutoff: u64) {
+        let expired: Vec<String> = self
+            .records
+            .iter()
+            .filter(|(_, record)| record.state.is_finished() && record.timestamp < cutoff)
+            .map(|(process_id, _)| process_id.clone())
+            .collect();
+        for process_id in expired {
+            self.archive(process_id);
+        }
+    }
+
+    fn archive(&mut self, process_id: String) {
+        if let Some(record) = self.records.remove(&process_id) {
+            self.pruned_count += 1;
+            if record.failure_reason.is_some() {
+                self.pruned_failures += 1;
+            }
+        }
+    }
+}
+
+/// Time a tally attempt may take, computation and opening included, before it counts as stalled.
+const TALLY_TIMEOUT_MILLIS: u64 = 60 * 60 * 1000;
+
+/// Number of tally attempts allowed before the ballot has to be abandoned.
+const MAX_TALLY_ATTEMPTS: u32 = 3;
+
+/// Maximum number of votes folded into the running total by a single `fold_votes` call.
+const FOLD_BATCH_SIZE: usize = 500;
+
+/// Time a proposed cancellation waits for confirmation before it expires.
+const CANCELLATION_WINDOW_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// Range of vote ids checked by a single range check, one bit each in the opened mask.
+const CHECK_BATCH_SIZE: u32 = 32;
+
+/// Time a range check may take, computation and opening included, before it counts as stalled.
+const VOTE_CHECK_TIMEOUT_MILLIS: u64 = 60 * 60 * 1000;
+
+/// Computes the receipt commitment a voter can recompute locally
+fn receipt_commitment(
+    ballot: Address,
+    voter: Address,
+    variable_id: u32,
+    input_transaction: Hash,
+) -> Hash {
+    let mut bytes: Vec<u8> = Vec::new();
+    WriteRPC::rpc_write_to(&ballot, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&voter, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&variable_id, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&input_transaction, &mut bytes).unwrap();
+    Hash::digest(&bytes)
+}
+
+/// Computes the hash of the inputs a private result was computed from
+fn private_result_inputs_hash(
+    ballot: Address,
+    variable_id: u32,
+    tallied_variables: &[u32],
+) -> Hash {
+    let mut bytes: Vec<u8> = Vec::new();
+    WriteRPC::rpc_write_to(&ballot, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&variable_id, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&tallied_variables.to_vec(), &mut bytes).unwrap();
+    Hash::digest(&bytes)
+}
+
+/// Canonical encoding of a ballot result, as attested by the ZK nodes.
+///
+/// The domain prefix is followed by the RPC encoding of the ballot address, the options, the
+/// six tally counts and the status:
+/// `"SEKIVA_BALLOT_RESULT_V1" || address (21 bytes) || u32 option count || (u32 length ||
+/// UTF-8 bytes) per option || option_0..option_4, total (u32 each) || status (u8)`.
+/// All integers are big-endian.
+fn result_attestation_data(
+    ballot: Address,
+    options: &[String],
+    tally: &Tally,
+    status: BallotStatus,
+) -> Vec<u8> {
+    let mut bytes: Vec<u8> = RESULT_ATTESTATION_DOMAIN.to_vec();
+    WriteRPC::rpc_write_to(&ballot, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&options.to_vec(), &mut bytes).unwrap();
+    for count in [
+        tally.option_0,
+        tally.option_1,
+        tally.option_2,
+        tally.option_3,
+        tally.option_4,
+        tally.total,
+    ] {
+        WriteRPC::rpc_write_to(&count, &mut bytes).unwrap();
+    }
+    WriteRPC::rpc_write_to(&status, &mut bytes).unwrap();
+    bytes
+}
+
+/// Records the current title, description and options of the ballot as a revision
+fn metadata_revision(
+    title: String,
+    description: String,
+    options: Vec<String>,
+    timestamp: u64,
+) -> MetadataRevision {
+    let mut bytes: Vec<u8> = Vec::new();
+    WriteRPC::rpc_write_to(&title, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&description, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&options, &mut bytes).unwrap();
+
+    MetadataRevision {
+        title,
+        description,
+        options,
+        content_hash: Hash::digest(&bytes),
+        timestamp,
+    }
+}
+
+/// Validates the budget of a budgeting ballot and the costs of its `project_count` projects
+fn validate_budget(budget: u32, costs: &[u32], project_count: usize) {
+    require!(
+        budget > 0 && budget <= MAX_BUDGET,
+        ContractError::InvalidBudget,
+        "Budget must be between 1 and {}",
+        MAX_BUDGET
+    );
+    require!(
+        costs.len() == project_count,
+        ContractError::InvalidBudget,
+        "Every project needs a cost"
+    );
+    require!(
+        costs.iter().all(|cost| *cost > 0 && *cost <= budget),
+        ContractError::InvalidBudget,
+        "Project costs must be between 1 and the budget"
+    );
+}
+
+/// Validates the format of an attachment
+fn validate_attachment(attachment: &Attachment) {
+    require!(
+        !attachment.uri.is_empty(),
+        ContractError::EmptyValue,
+        "Attachment URI cannot be empty"
+    );
+    require!(
+        attachment.sha256.len() == 64
+            && attachment
+                .sha256
+                .chars()
+                .all(|c| matches!(c, '0'..='9' | 'a'..='f')),
+        ContractError::InvalidAttachment,
+        "Attachment hash must be 64 lowercase hex characters"
+    );
+    require!(
+        attachment
+            .mime_type
+            .split_once('/')
+            .is_some_and(|(kind, subtype)| !kind.is_empty() && !subtype.is_empty()),
+        ContractError::InvalidAttachment,
+        "Attachment MIME type must be of the form type/subtype"
+    );
+}
+
+/// Collects the ids of all confirmed secret inputs: votes, anonymous voting token shares, draw
+/// contributions and budget allocations, and the running total of folded votes
+fn vote_variable_ids(zk_state: &ZkState<SecretVarType>) -> Vec<SecretVarId> {
+    zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, variable)| {
+            matches!(
+                variable.metadata,
+                SecretVarType::Vote {}
+                    | SecretVarType::AnonymousVote {}
+                    | SecretVarType::AdministratorTokenShare { .. }
+                    | SecretVarType::ApproverTokenShare { .. }
+                    | SecretVarType::DrawContribution {}
+                    | SecretVarType::BudgetAllocation {}
+                    | SecretVarType::PartialTally {}
+            )
+        })
+        .map(|(variable_id, _)| *variable_id)
+        .collect()
+}
+
+/// Ids of every vote variable a tally started now counts, including the votes already folded
+/// into the running total
+fn tallied_variable_ids(state: &BallotState, zk_state: &ZkState<SecretVarType>) -> Vec<u32> {
+    let mut variable_ids = state.folded_variables.clone();
+    variable_ids.extend(
+        vote_variable_ids(zk_state)
+            .iter()
+            .map(|variable_id| variable_id.raw_id),
+    );
+    variable_ids
+}
+
+/// Whether anyone has taken part in the ballot yet, by voting, contributing to the draw or
+/// allocating budget.
+///
+/// Issued token shares do not count, as every eligible voter gets them.
+fn participation_started(state: &BallotState, zk_state: &ZkState<SecretVarType>) -> bool {
+    !state.already_voted.is_empty()
+        || state.anonymous_votes > 0
+        || zk_state.secret_variables.iter().any(|(_, variable)| {
+            matches!(
+                variable.metadata,
+                SecretVarType::Vote {}
+                    | SecretVarType::AnonymousVote {}
+                    | SecretVarType::DrawContribution {}
+                    | SecretVarType::BudgetAllocation {}
+                    | SecretVarType::PartialTally {}
+            )
+        })
+}
+
+/// Starts the tally computation matching the mode of the ballot
+fn start_tally(state: &BallotState) -> ZkStateChange {
+    match &state.ballot_mode {
+        BallotMode::Vote {} => {}
+        BallotMode::Sortition { .. } => {
+            return zk_compute::combine_randomness_start(
+                Some(SHORTNAME_TALLY_COMPUTE_COMPLETE),
+                &[SecretVarType::DrawSeed {}],
+            );
+        }
+        BallotMode::Budgeting { budget, costs } => {
+            // Projects that do not exist cost 0
+            let cost = |project: usize| costs.get(project).copied().unwrap_or(0);
+            return zk_compute::allocate_budget_start(
+                *budget,
+                cost(0),
+                cost(1),
+                cost(2),
+                cost(3),
+                cost(4),
+                Some(SHORTNAME_TALLY_COMPUTE_COMPLETE),
+                &[SecretVarType::BudgetResult {}],
+            );
+        }
+    }
+    match state.participation_mode {
+        ParticipationMode::Public {} => zk_compute::tally_votes_start(
+            Some(SHORTNAME_TALLY_COMPUTE_COMPLETE),
+            &[SecretVarType::TallyResult {}],
+        ),
+        ParticipationMode::Anonymous { .. } => zk_compute::tally_anonymous_votes_start(
+            Some(SHORTNAME_TALLY_COMPUTE_COMPLETE),
+            &[SecretVarType::TallyResult {}],
+        ),
+    }
+}
+
+/// Hashes a voter address and its position in the snapshot into a leaf of the voter Merkle tree
+fn voter_leaf(index: u32, voter: &Address) -> Hash {
+    let mut bytes: Vec<u8> = vec![0x00];
+    WriteRPC::rpc_write_to(&index, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(voter, &mut bytes).unwrap();
+    Hash::digest(&bytes)
+}
+
+/// Hashes two sibling nodes of the voter Merkle tree
+///
+/// Siblings are ordered by value, so proofs do not need to carry the path. The position of the
+/// voter is part of the leaf instead.
+fn voter_node(left: &Hash, right: &Hash) -> Hash {
+    let (first, second) = if left.bytes <= right.bytes {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    let mut bytes: Vec<u8> = vec![0x01];
+    bytes.extend_from_slice(&first.bytes);
+    bytes.extend_from_slice(&second.bytes);
+    Hash::digest(&bytes)
+}
+
+/// Checks a Merkle proof that the voter is at `index` of the snapshot with the given root
+fn verify_voter_proof(root: &Hash, voter: &Address, index: u32, proof: &[Hash]) -> bool {
+    let computed_root = proof
+        .iter()
+        .fold(voter_leaf(index, voter), |node, sibling| {
+            voter_node(&node, sibling)
+        });
+    computed_root.bytes == root.bytes
+}
+
+/// Checks whether the address may start, fold, retry and abandon the tally
+fn is_tallier(state: &BallotState, address: &Address) -> bool {
+    if state.roles.talliers.is_empty() {
+        *address == state.administrator
+    } else {
+        state.roles.talliers.contains(address)
+    }
+}
+
+/// Checks whether the address may propose or confirm a cancellation
+///
+/// Without cancellers the administrator cancels on their own. Otherwise the administrator can
+/// only confirm a cancellation proposed by a canceller.
+fn is_canceller(state: &BallotState, address: &Address) -> bool {
+    state.roles.cancellers.contains(address)
+}
+
+/// Checks whether cancelling and syncing voters need approval by several approvers
+fn requires_approval(state: &BallotState) -> bool {
+    state.approval_policy.threshold > 1
 }
 
-/// Generates a unique process ID using timestamp and transaction bytes
-fn generate_process_id(ctx: &ContractContext) -> String {
-    let bytes_hex = ctx.original_transaction.bytes[0..8]
+/// Proposes the action unless it is pending already.
+///
+/// Actions whose approvals lapsed by `now` are dropped. Returns the pending actions and the
+/// index of the proposed action among them.
+fn propose_action(
+    pending_actions: &[PendingAction],
+    action: &BallotAction,
+    now: u64,
+    window_seconds: u64,
+) -> (Vec<PendingAction>, usize) {
+    let mut pending_actions: Vec<PendingAction> = pending_actions
+        .iter()
+        .filter(|pending| pending.expires_at >= now)
+        .cloned()
+        .collect();
+    let index = match pending_actions
         .iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<String>();
-    format!("{}-{}", ctx.block_time, bytes_hex)
+        .position(|pending| pending.action == *action)
+    {
+        Some(index) => index,
+        None => {
+            pending_actions.push(PendingAction {
+                action: action.clone(),
+                approvals: Vec::new(),
+                expires_at: now + window_seconds * 1000,
+            });
+            pending_actions.len() - 1
+        }
+    };
+    (pending_actions, index)
+}
+
+/// Records the approver's approval of the action, proposing the action if it is not pending.
+///
+/// Returns the pending actions and the index of the approved action among them.
+fn record_approval(
+    pending_actions: &[PendingAction],
+    action: &BallotAction,
+    approver: Address,
+    now: u64,
+    window_seconds: u64,
+) -> (Vec<PendingAction>, usize) {
+    let (mut pending_actions, index) = propose_action(pending_actions, action, now, window_seconds);
+    require!(
+        !pending_actions[index].approvals.contains(&approver),
+        ContractError::AlreadyApproved,
+        "Already approved the action"
+    );
+    pending_actions[index].approvals.push(approver);
+    (pending_actions, index)
+}
+
+/// Checks whether the voter may vote, taking changes since the snapshot into account
+fn is_eligible(
+    state: &BallotState,
+    voter: &Address,
+    voter_index: u32,
+    membership_proof: &[Hash],
+) -> bool {
+    if let Eligibility::TokenBalance { min_balance, .. } = state.eligibility {
+        return state
+            .token_balances
+            .get(voter)
+            .is_some_and(|balance| *balance >= min_balance);
+    }
+    if state.removed_voters.contains(voter) {
+        return false;
+    }
+    state.added_voters.contains(voter)
+        || verify_voter_proof(&state.voter_root, voter, voter_index, membership_proof)
+}
+
+/// Builds the event group reporting ballot events to the parent organization and the listener
+fn emit_ballot_events(state: &BallotState, events: Vec<BallotEvent>) -> Vec<EventGroup> {
+    let mut event_group = EventGroup::builder();
+    for event in events {
+        for receiver in std::iter::once(state.organization).chain(state.event_listener) {
+            event_group
+                .call(receiver, BALLOT_EVENT_SHORTNAME)
+                .argument(event.clone())
+                .done();
+        }
+    }
+    vec![event_group.build()]
 }
 
 /// Initializes contract
 ///
-/// Creates a new ballot with the given options.
+/// Creates a new ballot with the given options. Eligible voters are given as the Merkle root
+/// of the organization's member snapshot, voters prove their membership when casting a vote.
 #[init(zk = true)]
 fn initialize(
     ctx: ContractContext,
@@ -189,32 +1020,182 @@ fn initialize(
     description: String,
     organization: Address,
     administrator: Address,
-    eligible_voters: Vec<Address>,
+    voter_root: Hash,
+    voter_count: u32,
     duration_seconds: u64,
+    membership_policy: MembershipPolicy,
+    attachments: Vec<Attachment>,
+    eligibility: Eligibility,
+    result_disclosure: ResultDisclosure,
+    participation_mode: ParticipationMode,
+    ballot_mode: BallotMode,
+    runoff_rule: RunoffRule,
+    parent_ballot: Option<Address>,
+    roles: BallotRoles,
+    approval_policy: ApprovalPolicy,
 ) -> BallotState {
-    assert!(options.len() <= 5, "At most 5 options are supported");
-    assert!(options.len() > 1, "At least 2 options are required");
-    assert!(duration_seconds > 0, "Duration must be greater than 0");
-    assert!(
+    match &ballot_mode {
+        BallotMode::Vote {} | BallotMode::Budgeting { .. } => {
+            require!(
+                options.len() <= 5,
+                ContractError::InvalidOptionCount,
+                "At most 5 options are supported"
+            );
+            require!(
+                options.len() > 1,
+                ContractError::InvalidOptionCount,
+                "At least 2 options are required"
+            );
+        }
+        BallotMode::Sortition { .. } => {}
+    }
+    match &ballot_mode {
+        BallotMode::Vote {} => {}
+        BallotMode::Budgeting { budget, costs } => {
+            validate_budget(*budget, costs, options.len());
+            require!(
+                result_disclosure == ResultDisclosure::Public {}
+                    && participation_mode == ParticipationMode::Public {},
+                ContractError::WrongBallotMode,
+                "A budgeting ballot is always public"
+            );
+        }
+        &BallotMode::Sortition { seats } => {
+            require!(
+                seats > 0 && seats <= voter_count,
+                ContractError::InvalidSeatCount,
+                "Between 1 and {} seats can be drawn",
+                voter_count
+            );
+            require!(
+                result_disclosure == ResultDisclosure::Public {}
+                    && participation_mode == ParticipationMode::Public {},
+                ContractError::WrongBallotMode,
+                "A sortition draw is always public"
+            );
+            // Members are drawn by their position in the snapshot, which must not change
+            require!(
+                eligibility == Eligibility::Membership {}
+                    && membership_policy == MembershipPolicy::Snapshot {},
+                ContractError::MembershipPolicyViolation,
+                "A sortition draws from a fixed membership snapshot"
+            );
+        }
+    }
+    require!(
+        duration_seconds > 0,
+        ContractError::InvalidDuration,
+        "Duration must be greater than 0"
+    );
+    require!(
         duration_seconds <= 30 * 24 * 60 * 60,
+        ContractError::InvalidDuration,
         "Duration cannot exceed 30 days"
     );
-    assert!(
-        eligible_voters.len() >= 3,
+    require!(
+        voter_count >= 3,
+        ContractError::TooFewVoters,
         "At least 3 eligible voters are required to maintain vote privacy"
     );
+    if let ParticipationMode::Anonymous { issuance_seconds } = participation_mode {
+        require!(
+            issuance_seconds > 0 && issuance_seconds < duration_seconds,
+            ContractError::InvalidDuration,
+            "Token issuance must end before the voting period does"
+        );
+        require!(
+            approval_policy
+                .approvers
+                .iter()
+                .any(|approver| *approver != administrator),
+            ContractError::NoTokenCoIssuer,
+            "Anonymous voting needs an approver besides the administrator to co-issue tokens"
+        );
+    }
 
-    assert_ne!(
-        administrator, organization,
+    require!(
+        administrator != organization,
+        ContractError::InvalidAddress,
         "Administrator cannot be the organization."
     );
+    require!(
+        attachments.len() <= MAX_ATTACHMENTS,
+        ContractError::TooManyAttachments,
+        "At most {} attachments are supported",
+        MAX_ATTACHMENTS
+    );
+    attachments.iter().for_each(validate_attachment);
+    if let ResultDisclosure::Administrators { administrators } = &result_disclosure {
+        require!(
+            !administrators.is_empty(),
+            ContractError::EmptyValue,
+            "At least one administrator must be able to receive the result"
+        );
+    }
+    require!(
+        roles.cancellers.is_empty()
+            || roles
+                .cancellers
+                .iter()
+                .any(|canceller| *canceller != administrator),
+        ContractError::InvalidAddress,
+        "Cancellers must include someone besides the administrator to confirm a cancellation"
+    );
+    if approval_policy.threshold > 1 {
+        require!(
+            approval_policy.threshold as usize <= approval_policy.approvers.len(),
+            ContractError::InvalidApprovalPolicy,
+            "Approval threshold cannot exceed the number of approvers"
+        );
+        require!(
+            approval_policy.window_seconds > 0
+                && approval_policy.window_seconds <= 30 * 24 * 60 * 60,
+            ContractError::InvalidApprovalPolicy,
+            "Approval window must be between 1 second and 30 days"
+        );
+    }
+    if let RunoffRule::TopTwo {
+        duration_seconds: runoff_duration,
+    } = runoff_rule
+    {
+        require!(
+            ballot_mode == BallotMode::Vote {}
+                && result_disclosure == ResultDisclosure::Public {}
+                && parent_ballot.is_none(),
+            ContractError::InvalidRunoffRule,
+            "Only public votes that are not a runoff themselves can have a runoff"
+        );
+        require!(
+            options.len() > 2,
+            ContractError::InvalidRunoffRule,
+            "A runoff requires more than 2 options"
+        );
+        require!(
+            runoff_duration > 0 && runoff_duration <= 30 * 24 * 60 * 60,
+            ContractError::InvalidDuration,
+            "Runoff duration must be between 1 second and 30 days"
+        );
+        if let ParticipationMode::Anonymous { issuance_seconds } = participation_mode {
+            require!(
+                issuance_seconds < runoff_duration,
+                ContractError::InvalidDuration,
+                "Token issuance of the runoff must end before its voting period does"
+            );
+        }
+    }
 
-    // Generate a process ID for this ballot
-    let process_id = generate_process_id(&ctx);
+    // Allocate a process ID for this ballot
+    let mut process_counter = ProcessCounter::new();
+    let process_id = process_counter.next_id(ctx.contract_address);
     let start_time = ctx.block_production_time as u64;
     let end_time = start_time + duration_seconds * 1000;
+    let issuance_end = match participation_mode {
+        ParticipationMode::Anonymous { issuance_seconds } => start_time + issuance_seconds * 1000,
+        ParticipationMode::Public {} => start_time,
+    };
 
-    BallotState {
+    let mut state = BallotState {
+        state_version: STATE_VERSION,
         organization,
         administrator,
         title,
@@ -224,15 +1205,71 @@ fn initialize(
         end_time,
         status: Some(BallotStatus::Active {}),
         tally: None,
-        eligible_voters,
-        already_voted: Vec::new(),
+        voter_root,
+        voter_count,
+        added_voters: SortedVecSet::new(),
+        removed_voters: SortedVecSet::new(),
+        membership_policy,
+        already_voted: SortedVecSet::new(),
         process_state: BallotProcessState::Active {},
         process_id,
-        event_processes: SortedVecMap::new(),
-    }
+        event_processes: ProcessLog::new(),
+        process_counter,
+        tally_started_at: None,
+        tally_attempts: 0,
+        failure_reason: None,
+        vote_receipts: SortedVecMap::new(),
+        tallied_variables: Vec::new(),
+        event_listener: None,
+        paused_at: None,
+        metadata_revisions: Vec::new(),
+        attachments,
+        eligibility,
+        token_balances: SortedVecMap::new(),
+        result_disclosure,
+        private_result: None,
+        result_recipients: SortedVecSet::new(),
+        pending_recipient: None,
+        result_attestation: None,
+        participation_mode,
+        issuance_end,
+        administrator_shares: 0,
+        approver_shares: 0,
+        anonymous_votes: 0,
+        ballot_mode,
+        draw_contributors: Vec::new(),
+        draw_transcript: None,
+        runoff_rule,
+        parent_ballot,
+        budget_outcome: None,
+        partial_tally: None,
+        folding_votes: Vec::new(),
+        folded_variables: Vec::new(),
+        pending_checks: Vec::new(),
+        checking_votes: Vec::new(),
+        check_started_at: None,
+        rejected_voters: SortedVecSet::new(),
+        roles,
+        cancellation: None,
+        approval_policy,
+        pending_actions: Vec::new(),
+    };
+
+    let revision = metadata_revision(
+        state.title.clone(),
+        state.description.clone(),
+        state.options.clone(),
+        start_time,
+    );
+    state.metadata_revisions.push(revision);
+    state
 }
 
 /// Handles events from the organization contract
+///
+/// Membership changes are applied according to the ballot's `MembershipPolicy`, events the
+/// policy does not allow are marked as ignored. Members removed after voting keep their vote,
+/// removal only prevents them from voting from then on.
 #[action(shortname = 0x30, zk = true)]
 fn handle_org_event(
     ctx: ContractContext,
@@ -240,8 +1277,9 @@ fn handle_org_event(
     _zk_state: ZkState<SecretVarType>,
     event: OrganizationEvent,
 ) -> (BallotState, Vec<EventGroup>) {
-    assert_eq!(
-        ctx.sender, state.organization,
+    require!(
+        ctx.sender == state.organization,
+        ContractError::OnlyParentOrganization,
         "Only parent org can emit events"
     );
 
@@ -255,47 +1293,94 @@ fn handle_org_event(
 
     // Track the process
     let mut processes = state.event_processes.clone();
-    processes.insert(process_id.clone(), ProcessState::Received {});
+    processes.record(
+        process_id.clone(),
+        ProcessState::Received {},
+        ctx.block_production_time as u64,
+    );
 
     // Process based on ballot status
     match state.status {
-        Some(BallotStatus::Active {}) => {
-            let mut voters = state.eligible_voters.clone();
+        Some(BallotStatus::Active {}) | Some(BallotStatus::Paused {}) => {
+            let mut added_voters = state.added_voters.clone();
+            let mut removed_voters = state.removed_voters.clone();
 
             match event {
-                OrganizationEvent::MembersAdded { members, .. } => {
+                OrganizationEvent::MembersAdded { members, .. }
+                    if state.membership_policy != MembershipPolicy::Snapshot {} =>
+                {
                     // Add new members to eligible voters
-                    voters.extend(members.clone());
-                    processes.insert(process_id.clone(), ProcessState::Complete {});
+                    for member in &members {
+                        removed_voters.remove(member);
+                        added_voters.insert(*member);
+                    }
+                    processes.record(
+                        process_id.clone(),
+                        ProcessState::Complete {},
+                        ctx.block_production_time as u64,
+                    );
+
+                    let events = emit_ballot_events(
+                        &state,
+                        vec![BallotEvent::MembersUpdated {
+                            added: members,
+                            removed: vec![],
+                            timestamp: ctx.block_time as u64,
+                            process_id,
+                        }],
+                    );
 
-                    // Direct state update - no events
                     (
                         BallotState {
-                            eligible_voters: voters,
+                            added_voters,
+                            removed_voters,
                             event_processes: processes,
                             ..state
                         },
-                        vec![], // No events
+                        events,
                     )
                 }
-                OrganizationEvent::MembersRemoved { members, .. } => {
+                OrganizationEvent::MembersRemoved { members, .. }
+                    if state.membership_policy == MembershipPolicy::LiveSync {} =>
+                {
                     // Remove members from eligible voters
-                    let removed_members = members.clone();
-                    voters.retain(|voter| !members.contains(voter));
-                    processes.insert(process_id.clone(), ProcessState::Complete {});
+                    for member in &members {
+                        added_voters.remove(member);
+                        removed_voters.insert(*member);
+                    }
+                    processes.record(
+                        process_id.clone(),
+                        ProcessState::Complete {},
+                        ctx.block_production_time as u64,
+                    );
+
+                    let events = emit_ballot_events(
+                        &state,
+                        vec![BallotEvent::MembersUpdated {
+                            added: vec![],
+                            removed: members,
+                            timestamp: ctx.block_time as u64,
+                            process_id,
+                        }],
+                    );
 
-                    // Direct state update - no events
                     (
                         BallotState {
-                            eligible_voters: voters,
+                            added_voters,
+                            removed_voters,
                             event_processes: processes,
                             ..state
                         },
-                        vec![], // No events
+                        events,
                     )
                 }
                 _ => {
-                    processes.insert(process_id, ProcessState::Ignored {});
+                    processes.record_failure(
+                        process_id,
+                        ProcessState::Ignored {},
+                        ctx.block_production_time as u64,
+                        "Not applied under the ballot's membership policy".to_string(),
+                    );
                     (
                         BallotState {
                             event_processes: processes,
@@ -308,7 +1393,12 @@ fn handle_org_event(
         }
         _ => {
             // Mark as ignored if not in appropriate state
-            processes.insert(process_id, ProcessState::Ignored {});
+            processes.record_failure(
+                process_id,
+                ProcessState::Ignored {},
+                ctx.block_production_time as u64,
+                "Ballot no longer accepts membership changes".to_string(),
+            );
 
             (
                 BallotState {
@@ -321,71 +1411,728 @@ fn handle_org_event(
     }
 }
 
+/// Casts a secret vote.
+///
+/// The sender proves eligibility with a Merkle proof against `voter_root`, which may be empty
+/// for voters added after the snapshot was taken.
 #[zk_on_secret_input(shortname = 0x60, secret_type = "Sbi8")]
 fn cast_vote(
     context: ContractContext,
     state: BallotState,
     zk_state: ZkState<SecretVarType>,
+    voter_index: u32,
+    membership_proof: Vec<Hash>,
 ) -> (
     BallotState,
     Vec<EventGroup>,
     ZkInputDef<SecretVarType, Sbi8>,
 ) {
-    assert!(
-        state.status.unwrap() == BallotStatus::Active {},
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
         "Ballot is not active"
     );
-    assert!(
+    require!(
         context.block_time as u64 <= state.end_time,
+        ContractError::VotingEnded,
         "Voting period has ended"
     );
-    assert!(
-        state.eligible_voters.contains(&context.sender),
+    require!(
+        is_eligible(&state, &context.sender, voter_index, &membership_proof),
+        ContractError::NotEligible,
         "Not eligible to vote"
     );
-    assert!(
+    require!(
         !state.already_voted.contains(&context.sender),
+        ContractError::AlreadyVoted,
         "Already voted"
     );
+    require!(
+        state.participation_mode == ParticipationMode::Public {},
+        ContractError::WrongParticipationMode,
+        "Anonymous ballots only accept anonymous votes"
+    );
+    require!(
+        state.ballot_mode == BallotMode::Vote {},
+        ContractError::WrongBallotMode,
+        "Only voting ballots accept single votes"
+    );
 
+    let input_def = ZkInputDef::<SecretVarType, Sbi8>::with_metadata(
+        Some(SHORTNAME_VOTE_INPUTTED),
+        SecretVarType::Vote {},
+    );
+    let (state, events) = record_public_vote(&context, state);
+
+    (state, events, input_def)
+}
+
+/// Casts a secret budget allocation.
+///
+/// The sender proves eligibility like `cast_vote`, and distributes at most
+/// `zk_compute::MAX_ALLOCATION_POINTS` points across the projects. Invalid allocations are
+/// ignored by the tally.
+#[zk_on_secret_input(shortname = 0x67, secret_type = "zk_compute::BudgetAllocation")]
+fn cast_allocation(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    voter_index: u32,
+    membership_proof: Vec<Hash>,
+) -> (
+    BallotState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, zk_compute::BudgetAllocation>,
+) {
+    require!(
+        matches!(state.ballot_mode, BallotMode::Budgeting { .. }),
+        ContractError::WrongBallotMode,
+        "Ballot is not a budgeting ballot"
+    );
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
+    );
+    require!(
+        context.block_time as u64 <= state.end_time,
+        ContractError::VotingEnded,
+        "Voting period has ended"
+    );
+    require!(
+        is_eligible(&state, &context.sender, voter_index, &membership_proof),
+        ContractError::NotEligible,
+        "Not eligible to vote"
+    );
+    require!(
+        !state.already_voted.contains(&context.sender),
+        ContractError::AlreadyVoted,
+        "Already voted"
+    );
+
+    let input_def = ZkInputDef::<SecretVarType, zk_compute::BudgetAllocation>::with_metadata(
+        Some(SHORTNAME_VOTE_INPUTTED),
+        SecretVarType::BudgetAllocation {},
+    );
+    let (state, events) = record_public_vote(&context, state);
+
+    (state, events, input_def)
+}
+
+/// Marks the sender as voted and starts their receipt, for votes cast from the voter's address
+fn record_public_vote(
+    context: &ContractContext,
+    state: BallotState,
+) -> (BallotState, Vec<EventGroup>) {
     let mut voted = state.already_voted.clone();
-    voted.push(context.sender);
+    voted.insert(context.sender);
+    let mut rejected_voters = state.rejected_voters.clone();
+    rejected_voters.remove(&context.sender);
 
-    // Generate a process ID for this vote
-    let process_id = generate_process_id(&context);
+    // Allocate a process ID for this vote
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
 
     // Update process tracking directly in state instead of emitting self-event
     let mut processes = state.event_processes.clone();
-    processes.insert(process_id.clone(), ProcessState::Complete {});
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
 
-    let input_def = ZkInputDef::<SecretVarType, Sbi8>::with_metadata(
-        Some(SHORTNAME_VOTE_INPUTTED),
-        SecretVarType::Vote {},
+    // Start the receipt, it is completed once the input is confirmed
+    let mut receipts = state.vote_receipts.clone();
+    receipts.insert(
+        context.sender,
+        VoteReceipt {
+            input_transaction: context.original_transaction,
+            variable_id: None,
+            commitment: None,
+            timestamp: context.block_time as u64,
+            metadata_revision: state.metadata_revisions.len() as u32 - 1,
+        },
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::VoteCast {
+            voter: context.sender,
+            timestamp: context.block_time as u64,
+            process_id,
+        }],
     );
 
     (
         BallotState {
             already_voted: voted,
+            rejected_voters,
             event_processes: processes,
+            process_counter,
+            vote_receipts: receipts,
+            ..state
+        },
+        events,
+    )
+}
+
+/// Starts the check of the next batch of pending votes, if no check is running.
+///
+/// The batch holds the pending votes with ids in the `CHECK_BATCH_SIZE` ids from the lowest
+/// pending id, so votes confirmed while a check runs are checked together by the next one. An
+/// anonymous vote is checked against the votes with lower ids, so anonymous votes are only
+/// checked up to the first vote that is not confirmed yet. Callers make sure no other
+/// computation is running.
+fn start_next_vote_check(
+    state: &mut BallotState,
+    zk_state: &ZkState<SecretVarType>,
+    now: u64,
+) -> Vec<ZkStateChange> {
+    let Some(first_vote_id) = state.pending_checks.iter().min().copied() else {
+        return vec![];
+    };
+    if !state.checking_votes.is_empty() {
+        return vec![];
+    }
+    let unconfirmed = zk_state
+        .pending_inputs
+        .iter()
+        .filter(|(_, variable)| matches!(variable.metadata, SecretVarType::AnonymousVote {}))
+        .map(|(variable_id, _)| variable_id.raw_id)
+        .min();
+    let (batch, pending) = check_batch(&state.pending_checks, unconfirmed);
+    let Some(&last_vote_id) = batch.last() else {
+        return vec![];
+    };
+    state.pending_checks = pending;
+    state.checking_votes = batch;
+    state.check_started_at = Some(now);
+    let option_count = state.options.len() as u32;
+    let check = match state.participation_mode {
+        ParticipationMode::Public {} => zk_compute::check_votes_start(
+            first_vote_id,
+            option_count,
+            Some(SHORTNAME_VOTE_CHECKED),
+            &[SecretVarType::VoteCheck { first_vote_id }],
+        ),
+        ParticipationMode::Anonymous { .. } => zk_compute::check_anonymous_votes_start(
+            first_vote_id,
+            last_vote_id,
+            option_count,
+            Some(SHORTNAME_VOTE_CHECKED),
+            &[SecretVarType::VoteCheck { first_vote_id }],
+        ),
+    };
+    vec![check]
+}
+
+/// Splits the pending votes into the next batch to check, in id order, and the votes left
+/// pending.
+///
+/// The batch is empty while a vote with an id below the lowest pending one is `unconfirmed`.
+fn check_batch(pending_checks: &[u32], unconfirmed: Option<u32>) -> (Vec<u32>, Vec<u32>) {
+    let Some(first_vote_id) = pending_checks.iter().min().copied() else {
+        return (vec![], vec![]);
+    };
+    let (mut batch, pending): (Vec<u32>, Vec<u32>) =
+        pending_checks.iter().copied().partition(|vote_id| {
+            *vote_id - first_vote_id < CHECK_BATCH_SIZE
+                && unconfirmed.is_none_or(|unconfirmed| *vote_id < unconfirmed)
+        });
+    batch.sort_unstable();
+    (batch, pending)
+}
+
+/// Whether the running range check has not completed within `VOTE_CHECK_TIMEOUT_MILLIS`
+fn vote_check_stalled(state: &BallotState, now: u64) -> bool {
+    state
+        .check_started_at
+        .is_some_and(|started_at| now > started_at + VOTE_CHECK_TIMEOUT_MILLIS)
+}
+
+/// Automatically called when the range check of a batch of votes has been computed.
+///
+/// Only the mask of invalid votes is opened. A check without output keeps public votes
+/// unchecked, after which the next pending batch is checked. Anonymous votes are only counted
+/// once checked, so their batch is checked again instead.
+#[zk_on_compute_complete(shortname = 0x69)]
+fn vote_checked(
+    context: ContractContext,
+    mut state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    output_variables: Vec<SecretVarId>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    if output_variables.is_empty() {
+        let process_id = state.process_counter.next_id(context.contract_address);
+        state.event_processes.record_failure(
+            process_id,
+            ProcessState::Failed {},
+            context.block_production_time as u64,
+            "Vote check produced no output".to_string(),
+        );
+        let batch = std::mem::take(&mut state.checking_votes);
+        if let ParticipationMode::Anonymous { .. } = state.participation_mode {
+            state.pending_checks.extend(batch);
+        }
+        state.check_started_at = None;
+        let zk_state_changes =
+            start_next_vote_check(&mut state, &zk_state, context.block_production_time as u64);
+        return (state, vec![], zk_state_changes);
+    }
+
+    (
+        state,
+        vec![],
+        vec![ZkStateChange::OpenVariables {
+            variables: output_variables,
+        }],
+    )
+}
+
+/// Applies the opened range check of the running batch of votes.
+///
+/// Bit `i` of `invalid_mask` is set if vote `first_vote_id + i` is invalid. An invalid vote is
+/// deleted and its voter may vote again. The voter is told through a `VoteRejected` event and
+/// `rejected_voters`, the vote itself is never revealed. An invalid anonymous vote only frees
+/// its place among the anonymous votes, as its sender is not a voter. The result of a check
+/// that was superseded by `reset_vote_check` is only deleted.
+fn apply_vote_check(
+    context: &ContractContext,
+    mut state: BallotState,
+    zk_state: &ZkState<SecretVarType>,
+    check_id: SecretVarId,
+    first_vote_id: u32,
+    invalid_mask: u32,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let mut variables_to_delete = vec![check_id];
+    let mut rejections = vec![];
+
+    if state.checking_votes.first() == Some(&first_vote_id) {
+        let batch = std::mem::take(&mut state.checking_votes);
+        state.check_started_at = None;
+        for vote_id in batch {
+            if invalid_mask & (1 << (vote_id - first_vote_id)) == 0 {
+                continue;
+            }
+            if let Some(vote) = zk_state.get_variable(SecretVarId::new(vote_id)) {
+                variables_to_delete.push(SecretVarId::new(vote_id));
+                if let SecretVarType::AnonymousVote {} = vote.metadata {
+                    state.anonymous_votes -= 1;
+                    continue;
+                }
+                let voter = vote.owner;
+                state.already_voted.remove(&voter);
+                state.vote_receipts.remove(&voter);
+                state.rejected_voters.insert(voter);
+
+                let process_id = state.process_counter.next_id(context.contract_address);
+                state.event_processes.record(
+                    process_id.clone(),
+                    ProcessState::Complete {},
+                    context.block_production_time as u64,
+                );
+                rejections.push(BallotEvent::VoteRejected {
+                    voter,
+                    timestamp: context.block_time as u64,
+                    process_id,
+                });
+            }
+        }
+    }
+
+    let events = if rejections.is_empty() {
+        vec![]
+    } else {
+        emit_ballot_events(&state, rejections)
+    };
+    let mut zk_state_changes = vec![ZkStateChange::DeleteVariables {
+        variables_to_delete,
+    }];
+    zk_state_changes.extend(start_next_vote_check(
+        &mut state,
+        zk_state,
+        context.block_production_time as u64,
+    ));
+    (state, events, zk_state_changes)
+}
+
+/// Allows a tallier to recover a range check that has stalled.
+///
+/// Once the running check has not completed within `VOTE_CHECK_TIMEOUT_MILLIS`, its batch is
+/// checked again, or with `skip` kept without a check, like the votes of a check without
+/// output. Anonymous votes cannot be skipped, as only checked anonymous votes are counted. A
+/// result of the stalled check that arrives later is deleted.
+#[action(shortname = 0x04, zk = true)]
+fn reset_vote_check(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    skip: bool,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        is_tallier(&state, &context.sender),
+        ContractError::OnlyTallier,
+        "Only talliers can reset vote checks"
+    );
+    require!(
+        state.check_started_at.is_some(),
+        ContractError::NoVoteCheck,
+        "No vote check is running"
+    );
+    let now = context.block_production_time as u64;
+    require!(
+        vote_check_stalled(&state, now),
+        ContractError::ComputationInProgress,
+        "Vote check is still in progress"
+    );
+    require!(
+        !skip || state.participation_mode == ParticipationMode::Public {},
+        ContractError::WrongParticipationMode,
+        "Anonymous votes cannot be kept without a check"
+    );
+
+    let mut process_counter = state.process_counter.clone();
+    let mut processes = state.event_processes.clone();
+    processes.record_failure(
+        process_counter.next_id(context.contract_address),
+        ProcessState::Failed {},
+        now,
+        format!(
+            "Vote check did not complete within {} seconds",
+            VOTE_CHECK_TIMEOUT_MILLIS / 1000
+        ),
+    );
+
+    let mut pending_checks = if skip {
+        Vec::new()
+    } else {
+        state.checking_votes.clone()
+    };
+    pending_checks.extend(state.pending_checks.iter().copied());
+
+    let mut state = BallotState {
+        pending_checks,
+        checking_votes: Vec::new(),
+        check_started_at: None,
+        event_processes: processes,
+        process_counter,
+        ..state
+    };
+    let zk_state_changes = start_next_vote_check(&mut state, &zk_state, now);
+    (state, vec![], zk_state_changes)
+}
+
+/// Issues a share of the secret one-time token of a voter on an anonymous ballot.
+///
+/// The token of a voter consists of two random 128-bit shares, one issued by the administrator
+/// and one by another approver, so neither issuer knows a whole token. Shares are issued for
+/// every voter of the snapshot from the creation of the ballot until `issuance_end`, before the
+/// first anonymous vote. Each share is transferred to its voter once confirmed. Holding shares
+/// therefore reveals nothing about who votes.
+#[zk_on_secret_input(shortname = 0x64, secret_type = "Sbi128")]
+fn issue_token_share(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    voter: Address,
+    voter_index: u32,
+    membership_proof: Vec<Hash>,
+) -> (
+    BallotState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, Sbi128>,
+) {
+    require!(
+        matches!(
+            state.participation_mode,
+            ParticipationMode::Anonymous { .. }
+        ),
+        ContractError::WrongParticipationMode,
+        "Ballot does not use anonymous voting"
+    );
+    require!(
+        state.ballot_mode == BallotMode::Vote {},
+        ContractError::WrongBallotMode,
+        "Sortition ballots do not accept votes"
+    );
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
+    );
+    require!(
+        context.block_production_time as u64 <= state.issuance_end,
+        ContractError::IssuanceClosed,
+        "Token issuance has closed"
+    );
+    let by_administrator = context.sender == state.administrator;
+    require!(
+        by_administrator || state.approval_policy.approvers.contains(&context.sender),
+        ContractError::OnlyApprover,
+        "Only the administrator and approvers can issue token shares"
+    );
+    require!(
+        is_eligible(&state, &voter, voter_index, &membership_proof),
+        ContractError::NotEligible,
+        "Not eligible to vote"
+    );
+    // One share of each kind per voter bounds the tokens, and with them the anonymous votes
+    let already_issued = zk_state
+        .secret_variables
+        .iter()
+        .chain(zk_state.pending_inputs.iter())
+        .any(|(_, variable)| match variable.metadata {
+            SecretVarType::AdministratorTokenShare { voter: holder } => {
+                by_administrator && holder == voter
+            }
+            SecretVarType::ApproverTokenShare { voter: holder } => {
+                !by_administrator && holder == voter
+            }
+            _ => false,
+        });
+    require!(
+        !already_issued,
+        ContractError::TokenShareIssued,
+        "A token share was already issued to the voter"
+    );
+
+    if by_administrator {
+        let input_def = ZkInputDef::<SecretVarType, Sbi128>::with_metadata(
+            None,
+            SecretVarType::AdministratorTokenShare { voter },
+        );
+        (
+            BallotState {
+                administrator_shares: state.administrator_shares + 1,
+                ..state
+            },
+            vec![],
+            input_def,
+        )
+    } else {
+        let input_def = ZkInputDef::<SecretVarType, Sbi128>::with_metadata(
+            None,
+            SecretVarType::ApproverTokenShare { voter },
+        );
+        (
+            BallotState {
+                approver_shares: state.approver_shares + 1,
+                ..state
+            },
+            vec![],
+            input_def,
+        )
+    }
+}
+
+/// Number of voters that can hold a whole token, an administrator and an approver share
+fn issued_tokens(state: &BallotState) -> u32 {
+    state.administrator_shares.min(state.approver_shares)
+}
+
+/// Casts an anonymous vote with the shares of an issued one-time token.
+///
+/// No eligibility is checked and the sender is not recorded, so the vote can be sent from any
+/// address. Voting opens once token issuance has closed, and at most one vote per issued token
+/// is held, which bounds the cost of the tally. Each vote is checked like a public vote: a vote
+/// whose shares were not issued, whose token was used by an earlier vote or whose option does
+/// not exist is deleted, which frees its place for the vote of an actual voter.
+#[zk_on_secret_input(shortname = 0x65, secret_type = "zk_compute::AnonymousBallot")]
+fn cast_anonymous_vote(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+) -> (
+    BallotState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, zk_compute::AnonymousBallot>,
+) {
+    require!(
+        matches!(
+            state.participation_mode,
+            ParticipationMode::Anonymous { .. }
+        ),
+        ContractError::WrongParticipationMode,
+        "Ballot does not use anonymous voting"
+    );
+    require!(
+        state.ballot_mode == BallotMode::Vote {},
+        ContractError::WrongBallotMode,
+        "Sortition ballots do not accept votes"
+    );
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
+    );
+    require!(
+        context.block_production_time as u64 > state.issuance_end,
+        ContractError::IssuanceOpen,
+        "Voting opens once token issuance has closed"
+    );
+    require!(
+        context.block_time as u64 <= state.end_time,
+        ContractError::VotingEnded,
+        "Voting period has ended"
+    );
+    require!(
+        state.anonymous_votes < issued_tokens(&state),
+        ContractError::VoteLimitReached,
+        "The anonymous votes fill the issued tokens, retry once pending votes are checked"
+    );
+
+    let input_def = ZkInputDef::<SecretVarType, zk_compute::AnonymousBallot>::with_metadata(
+        None,
+        SecretVarType::AnonymousVote {},
+    );
+
+    (
+        BallotState {
+            anonymous_votes: state.anonymous_votes + 1,
+            ..state
+        },
+        vec![],
+        input_def,
+    )
+}
+
+/// Contributes secret randomness to the seed of a sortition draw.
+///
+/// The sender proves eligibility like `cast_vote` and contributes once. Contributing does not
+/// make the sender a candidate, every member of the snapshot can be drawn. The contributions
+/// are combined into the seed of the draw once the administrator closes it with
+/// `compute_tally`, so the seed is unpredictable as long as one contributor is honest.
+#[zk_on_secret_input(shortname = 0x66, secret_type = "Sbi128")]
+fn contribute_randomness(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    voter_index: u32,
+    membership_proof: Vec<Hash>,
+) -> (
+    BallotState,
+    Vec<EventGroup>,
+    ZkInputDef<SecretVarType, Sbi128>,
+) {
+    require!(
+        matches!(state.ballot_mode, BallotMode::Sortition { .. }),
+        ContractError::WrongBallotMode,
+        "Ballot is not a sortition"
+    );
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
+    );
+    require!(
+        context.block_time as u64 <= state.end_time,
+        ContractError::VotingEnded,
+        "Contribution period has ended"
+    );
+    require!(
+        is_eligible(&state, &context.sender, voter_index, &membership_proof),
+        ContractError::NotEligible,
+        "Not eligible to contribute to the draw"
+    );
+    require!(
+        !state.draw_contributors.contains(&context.sender),
+        ContractError::AlreadyContributed,
+        "Already contributed to the draw"
+    );
+
+    let mut draw_contributors = state.draw_contributors.clone();
+    draw_contributors.push(context.sender);
+
+    let input_def = ZkInputDef::<SecretVarType, Sbi128>::with_metadata(
+        None,
+        SecretVarType::DrawContribution {},
+    );
+
+    (
+        BallotState {
+            draw_contributors,
             ..state
         },
-        vec![], // No events
+        vec![],
         input_def,
     )
 }
 
+/// Automatically called when a secret input has been confirmed by the ZK nodes.
+///
+/// Completes the voter's receipt with the id of the secret variable holding their vote, and
+/// queues a vote for its check. A token share is transferred to its voter.
 #[zk_on_variable_inputted(shortname = 0x61)]
 fn vote_inputted(
     context: ContractContext,
     state: BallotState,
     zk_state: ZkState<SecretVarType>,
     inputted_variable: SecretVarId,
-) -> BallotState {
-    assert!(
-        state.status.unwrap() == BallotStatus::Active {},
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    // Votes cast before a pause are still confirmed while paused
+    require!(
+        matches!(
+            state.status,
+            Some(BallotStatus::Active {}) | Some(BallotStatus::Paused {})
+        ),
+        ContractError::BallotNotActive,
         "Ballot is not active"
     );
-    state
+
+    let Some(variable) = zk_state.get_variable(inputted_variable) else {
+        fail(
+            ContractError::MissingVariable,
+            "Inputted variable not found",
+        )
+    };
+    if let SecretVarType::AdministratorTokenShare { voter }
+    | SecretVarType::ApproverTokenShare { voter } = variable.metadata
+    {
+        return (
+            state,
+            vec![],
+            vec![ZkStateChange::TransferVariable {
+                variable: inputted_variable,
+                new_owner: voter,
+            }],
+        );
+    }
+
+    let voter = variable.owner;
+    let mut receipts = state.vote_receipts.clone();
+    if let Some(receipt) = receipts.get(&voter).cloned() {
+        receipts.insert(
+            voter,
+            VoteReceipt {
+                variable_id: Some(inputted_variable.raw_id),
+                commitment: Some(receipt_commitment(
+                    context.contract_address,
+                    voter,
+                    inputted_variable.raw_id,
+                    receipt.input_transaction,
+                )),
+                ..receipt
+            },
+        );
+    }
+
+    let mut state = BallotState {
+        vote_receipts: receipts,
+        ..state
+    };
+
+    // Checks run one at a time, a check waiting for another computation is started when that
+    // computation completes
+    let mut zk_state_changes = vec![];
+    if let SecretVarType::Vote {} | SecretVarType::AnonymousVote {} = variable.metadata {
+        state.pending_checks.push(inputted_variable.raw_id);
+        if zk_state.calculation_state == CalculationStatus::Waiting {
+            zk_state_changes =
+                start_next_vote_check(&mut state, &zk_state, context.block_production_time as u64);
+        }
+    }
+
+    (state, vec![], zk_state_changes)
 }
 
 /// Allows the administrator to start the computation of the tally.
@@ -397,84 +2144,640 @@ fn compute_tally(
     state: BallotState,
     zk_state: ZkState<SecretVarType>,
 ) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
-    assert_eq!(
-        context.sender, state.administrator,
-        "Only administrator can start computation"
+    require!(
+        is_tallier(&state, &context.sender),
+        ContractError::OnlyTallier,
+        "Only talliers can start computation"
+    );
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
     );
-    assert_eq!(
-        zk_state.calculation_state,
-        CalculationStatus::Waiting,
+    require!(
+        zk_state.calculation_state == CalculationStatus::Waiting,
+        ContractError::ComputationInProgress,
         "Computation must start from Waiting state, but was {:?}",
-        zk_state.calculation_state,
+        zk_state.calculation_state
     );
-    assert!(
-        state.already_voted.len() >= 3,
-        "At least 3 votes are required before tallying can begin"
+    require!(
+        state.pending_checks.is_empty() && state.checking_votes.is_empty(),
+        ContractError::ComputationInProgress,
+        "Votes are still being checked"
     );
+    match state.ballot_mode {
+        BallotMode::Vote {} | BallotMode::Budgeting { .. } => require!(
+            state.already_voted.len() >= 3
+                || (issued_tokens(&state) >= 3 && state.anonymous_votes >= 3),
+            ContractError::TooFewVotes,
+            "At least 3 votes are required before tallying can begin"
+        ),
+        BallotMode::Sortition { .. } => require!(
+            !state.draw_contributors.is_empty(),
+            ContractError::NoDrawContributions,
+            "At least one member must contribute randomness to the draw"
+        ),
+    }
 
-    // Generate a process ID for this tally
-    let process_id = generate_process_id(&context);
+    // Allocate a process ID for this tally
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
 
     // Update process tracking directly in state instead of emitting self-event
     let mut processes = state.event_processes.clone();
-    processes.insert(process_id.clone(), ProcessState::Complete {});
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![
+            BallotEvent::TallyStarted {
+                timestamp: context.block_time as u64,
+                process_id: process_id.clone(),
+            },
+            BallotEvent::StatusChanged {
+                status: BallotStatus::Tallying {},
+                timestamp: context.block_time as u64,
+                process_id,
+            },
+        ],
+    );
 
     (
         BallotState {
             status: Some(BallotStatus::Tallying {}),
             process_state: BallotProcessState::Tallying {},
             event_processes: processes,
+            process_counter,
+            tally_started_at: Some(context.block_production_time as u64),
+            tally_attempts: 1,
+            failure_reason: None,
+            tallied_variables: tallied_variable_ids(&state, &zk_state),
             ..state
         },
-        vec![], // No events
-        vec![zk_compute::tally_votes_start(
-            Some(SHORTNAME_TALLY_COMPUTE_COMPLETE),
-            &[SecretVarType::TallyResult {}],
-        )],
+        events,
+        vec![start_tally(&state)],
     )
 }
 
-/// Automatically called when the computation is completed
+/// Allows the administrator to fold a batch of votes into a secret running total.
 ///
-/// The only thing we do is to instantly open/declassify the output variables.
-#[zk_on_compute_complete(shortname = 0x62)]
-fn tally_compute_complete(
+/// Up to `FOLD_BATCH_SIZE` of the oldest checked votes are added to the running total under
+/// MPC, after which they are deleted, see `fold_batch`. Folding periodically while voting keeps the final tally
+/// within computation limits for large electorates, as it only combines the running total with
+/// the votes cast since the last fold. Only public votes can be folded, anonymous votes are
+/// checked against all other anonymous votes when tallied.
+#[action(shortname = 0x0D, zk = true)]
+fn fold_votes(
     context: ContractContext,
     state: BallotState,
     zk_state: ZkState<SecretVarType>,
-    output_variables: Vec<SecretVarId>,
 ) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        is_tallier(&state, &context.sender),
+        ContractError::OnlyTallier,
+        "Only talliers can fold votes"
+    );
+    require!(
+        matches!(
+            state.status,
+            Some(BallotStatus::Active {}) | Some(BallotStatus::Paused {})
+        ),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
+    );
+    require!(
+        state.ballot_mode == BallotMode::Vote {},
+        ContractError::WrongBallotMode,
+        "Only voting ballots can fold votes"
+    );
+    require!(
+        state.participation_mode == ParticipationMode::Public {},
+        ContractError::WrongParticipationMode,
+        "Anonymous votes cannot be folded"
+    );
+    require!(
+        zk_state.calculation_state == CalculationStatus::Waiting,
+        ContractError::ComputationInProgress,
+        "Computation must start from Waiting state, but was {:?}",
+        zk_state.calculation_state
+    );
+
+    let vote_ids: Vec<u32> = zk_state
+        .secret_variables
+        .iter()
+        .filter(|(_, variable)| matches!(variable.metadata, SecretVarType::Vote {}))
+        .map(|(variable_id, _)| variable_id.raw_id)
+        .collect();
+    // Votes that are not confirmed yet are not checked either
+    let mut unchecked: Vec<u32> = zk_state
+        .pending_inputs
+        .iter()
+        .filter(|(_, variable)| matches!(variable.metadata, SecretVarType::Vote {}))
+        .map(|(variable_id, _)| variable_id.raw_id)
+        .collect();
+    unchecked.extend(state.pending_checks.iter().copied());
+    unchecked.extend(state.checking_votes.iter().copied());
+
+    let batch = fold_batch(&vote_ids, &unchecked);
+    let (Some(&first_vote_id), Some(&last_vote_id)) = (batch.first(), batch.last()) else {
+        fail(ContractError::TooFewVotes, "No checked votes to fold")
+    };
+
     (
-        state,
+        BallotState {
+            folding_votes: batch,
+            ..state
+        },
         vec![],
-        vec![ZkStateChange::OpenVariables {
-            variables: output_variables,
-        }],
+        vec![zk_compute::fold_votes_start(
+            first_vote_id,
+            last_vote_id,
+            Some(SHORTNAME_VOTES_FOLDED),
+            &[SecretVarType::PartialTally {}],
+        )],
     )
 }
 
-/// Automatically called when a variable is opened/declassified.
+/// Automatically called when a batch of votes has been folded into a new running total.
 ///
-/// We can now read the sum variable, and compute the average, which will be our final result.
-#[zk_on_variables_opened]
-fn open_tally_result(
+/// The folded votes and the previous running total are deleted. A fold without output keeps
+/// the votes, so they are folded again by a later fold or the final tally.
+#[zk_on_compute_complete(shortname = 0x68)]
+fn votes_folded(
     context: ContractContext,
-    mut state: BallotState,
+    state: BallotState,
     zk_state: ZkState<SecretVarType>,
-    opened_variables: Vec<SecretVarId>,
+    output_variables: Vec<SecretVarId>,
 ) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
-    assert_eq!(
-        opened_variables.len(),
-        1,
-        "Unexpected number of output variables"
-    );
-
-    let opened_variable = zk_state
-        .get_variable(*opened_variables.first().unwrap())
-        .unwrap();
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
+    let mut processes = state.event_processes.clone();
 
-    // Use our helper function to read the TallyResult
-    let tally_result = read_variable(&zk_state, &opened_variable.variable_id);
+    if output_variables.is_empty() {
+        processes.record_failure(
+            process_id,
+            ProcessState::Failed {},
+            context.block_production_time as u64,
+            "Vote folding produced no output".to_string(),
+        );
+        let mut state = BallotState {
+            folding_votes: Vec::new(),
+            event_processes: processes,
+            process_counter,
+            ..state
+        };
+        let zk_state_changes =
+            start_next_vote_check(&mut state, &zk_state, context.block_production_time as u64);
+        return (state, vec![], zk_state_changes);
+    }
+    processes.record(
+        process_id,
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    let (folded_variables, variables_to_delete) = complete_fold(
+        &state.folding_votes,
+        &state.folded_variables,
+        state.partial_tally,
+    );
+
+    let mut state = BallotState {
+        partial_tally: Some(output_variables[0].raw_id),
+        folded_variables,
+        folding_votes: Vec::new(),
+        event_processes: processes,
+        process_counter,
+        ..state
+    };
+
+    // Votes confirmed while folding are checked now
+    let mut zk_state_changes = vec![ZkStateChange::DeleteVariables {
+        variables_to_delete,
+    }];
+    zk_state_changes.extend(start_next_vote_check(
+        &mut state,
+        &zk_state,
+        context.block_production_time as u64,
+    ));
+    (state, vec![], zk_state_changes)
+}
+
+/// Selects the votes the next fold adds to the running total.
+///
+/// The batch is the run of the oldest votes up to the first one in `unchecked`, at most
+/// `FOLD_BATCH_SIZE` votes. The fold computation adds every vote with an id from the first to
+/// the last of the batch, so the batch must not skip a vote in between.
+fn fold_batch(vote_ids: &[u32], unchecked: &[u32]) -> Vec<u32> {
+    let mut vote_ids = vote_ids.to_vec();
+    vote_ids.extend(unchecked.iter().copied());
+    vote_ids.sort_unstable();
+    vote_ids.dedup();
+    vote_ids
+        .into_iter()
+        .take_while(|vote_id| !unchecked.contains(vote_id))
+        .take(FOLD_BATCH_SIZE)
+        .collect()
+}
+
+/// Records a completed fold of `folding_votes`.
+///
+/// Returns the ids of all votes folded so far, kept for the inclusion check as the folded votes
+/// are deleted, and the variables to delete: the folded votes and the previous running total.
+fn complete_fold(
+    folding_votes: &[u32],
+    folded_variables: &[u32],
+    partial_tally: Option<u32>,
+) -> (Vec<u32>, Vec<SecretVarId>) {
+    let mut folded = folded_variables.to_vec();
+    folded.extend(folding_votes.iter().copied());
+
+    let mut variables_to_delete: Vec<SecretVarId> = folding_votes
+        .iter()
+        .map(|raw_id| SecretVarId::new(*raw_id))
+        .collect();
+    variables_to_delete.extend(partial_tally.map(SecretVarId::new));
+    (folded, variables_to_delete)
+}
+
+/// Determines why the running tally can no longer be expected to complete.
+///
+/// Returns `None` while the tally is still progressing normally.
+fn tally_failure_reason(
+    context: &ContractContext,
+    state: &BallotState,
+    zk_state: &ZkState<SecretVarType>,
+) -> Option<String> {
+    if zk_state.calculation_state == CalculationStatus::MaliciousBehaviour {
+        return Some("Malicious behaviour detected during computation".to_string());
+    }
+    if let Some(reason) = &state.failure_reason {
+        return Some(reason.clone());
+    }
+    let started_at = state.tally_started_at?;
+    if context.block_production_time as u64 > started_at + TALLY_TIMEOUT_MILLIS {
+        return Some(format!(
+            "Tally did not complete within {} seconds",
+            TALLY_TIMEOUT_MILLIS / 1000
+        ));
+    }
+    None
+}
+
+/// Allows the administrator to retry a tally that has failed or stalled.
+///
+/// If the tally result was already computed, only the opening is retried. Otherwise the
+/// computation is started again from scratch. Once the tally has failed or timed out this works
+/// from any calculation status, so a computation stuck in `Calculating` or `Output`, or one that
+/// ended in `MaliciousBehaviour`, can be recovered too.
+#[action(shortname = 0x02, zk = true)]
+fn retry_tally(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        is_tallier(&state, &context.sender),
+        ContractError::OnlyTallier,
+        "Only talliers can retry computation"
+    );
+    require!(
+        state.status == Some(BallotStatus::Tallying {}),
+        ContractError::BallotNotTallying,
+        "Ballot is not tallying"
+    );
+    require!(
+        tally_failure_reason(&context, &state, &zk_state).is_some(),
+        ContractError::TallyInProgress,
+        "Tally is still in progress"
+    );
+    require!(
+        state.tally_attempts < MAX_TALLY_ATTEMPTS,
+        ContractError::TallyAttemptsExhausted,
+        "Tally has already been attempted {} times, abandon it instead",
+        state.tally_attempts
+    );
+
+    // Allocate a process ID for this retry
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
+
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    // Reuse an already computed result rather than computing it again
+    let computed_result = zk_state
+        .secret_variables
+        .iter()
+        .find(|(_, variable)| {
+            matches!(
+                variable.metadata,
+                SecretVarType::TallyResult {}
+                    | SecretVarType::DrawSeed {}
+                    | SecretVarType::BudgetResult {}
+            )
+        })
+        .map(|(variable_id, _)| *variable_id);
+
+    let zk_state_change = match computed_result {
+        Some(variable_id) => ZkStateChange::OpenVariables {
+            variables: vec![variable_id],
+        },
+        None => start_tally(&state),
+    };
+
+    // A restarted computation consumes the votes confirmed by now
+    let tallied_variables = match computed_result {
+        Some(_) => state.tallied_variables.clone(),
+        None => tallied_variable_ids(&state, &zk_state),
+    };
+
+    (
+        BallotState {
+            event_processes: processes,
+            process_counter,
+            tallied_variables,
+            tally_started_at: Some(context.block_production_time as u64),
+            tally_attempts: state.tally_attempts + 1,
+            failure_reason: None,
+            ..state
+        },
+        vec![],
+        vec![zk_state_change],
+    )
+}
+
+/// Allows the administrator to formally abandon a tally that has failed or stalled.
+///
+/// The ballot ends in the terminal `TallyFailed` status, recording the detected failure
+/// together with the administrator's note. All secret variables are deleted, so no votes or
+/// partial results outlive the abandoned tally.
+#[action(shortname = 0x03, zk = true)]
+fn abandon_tally(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    note: String,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        is_tallier(&state, &context.sender),
+        ContractError::OnlyTallier,
+        "Only talliers can abandon computation"
+    );
+    require!(
+        state.status == Some(BallotStatus::Tallying {}),
+        ContractError::BallotNotTallying,
+        "Ballot is not tallying"
+    );
+    let detected_reason = tally_failure_reason(&context, &state, &zk_state).unwrap_or_else(|| {
+        fail(
+            ContractError::TallyInProgress,
+            "Tally is still in progress and cannot be abandoned",
+        )
+    });
+
+    let reason = if note.is_empty() {
+        detected_reason
+    } else {
+        format!("{}: {}", detected_reason, note)
+    };
+
+    // Allocate a process ID for this abandonment
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
+
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::StatusChanged {
+            status: BallotStatus::TallyFailed {},
+            timestamp: context.block_time as u64,
+            process_id,
+        }],
+    );
+
+    let secret_variables = zk_state
+        .secret_variables
+        .iter()
+        .map(|(variable_id, _)| *variable_id)
+        .collect();
+
+    (
+        BallotState {
+            status: Some(BallotStatus::TallyFailed {}),
+            process_state: BallotProcessState::TallyFailed {},
+            event_processes: processes,
+            process_counter,
+            tally_started_at: None,
+            failure_reason: Some(reason),
+            ..state
+        },
+        events,
+        vec![
+            ZkStateChange::DeleteVariables {
+                variables_to_delete: secret_variables,
+            },
+            ZkStateChange::ContractDone,
+        ],
+    )
+}
+
+/// Automatically called when the computation is completed
+///
+/// The only thing we do is to instantly open/declassify the output variables.
+/// A computation without output is recorded as failed so the administrator can retry it.
+/// Output of a computation that completes after the tally was already settled by a retry is
+/// deleted.
+#[zk_on_compute_complete(shortname = 0x62)]
+fn tally_compute_complete(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    output_variables: Vec<SecretVarId>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    if state.status != Some(BallotStatus::Tallying {}) {
+        return (
+            state,
+            vec![],
+            vec![ZkStateChange::DeleteVariables {
+                variables_to_delete: output_variables,
+            }],
+        );
+    }
+
+    if output_variables.is_empty() {
+        let reason = "Tally computation produced no output".to_string();
+        let mut process_counter = state.process_counter.clone();
+        let mut processes = state.event_processes.clone();
+        processes.record_failure(
+            process_counter.next_id(context.contract_address),
+            ProcessState::Failed {},
+            context.block_production_time as u64,
+            reason.clone(),
+        );
+
+        return (
+            BallotState {
+                failure_reason: Some(reason),
+                event_processes: processes,
+                process_counter,
+                ..state
+            },
+            vec![],
+            vec![],
+        );
+    }
+
+    if matches!(state.result_disclosure, ResultDisclosure::Public {}) {
+        return (
+            state,
+            vec![],
+            vec![ZkStateChange::OpenVariables {
+                variables: output_variables,
+            }],
+        );
+    }
+
+    // A private result is never opened, recipients request their own copy instead
+    let variable_id = output_variables[0].raw_id;
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![
+            BallotEvent::TallyCompleted {
+                timestamp: context.block_time as u64,
+                process_id: process_id.clone(),
+            },
+            BallotEvent::StatusChanged {
+                status: BallotStatus::Completed {},
+                timestamp: context.block_time as u64,
+                process_id,
+            },
+        ],
+    );
+
+    (
+        BallotState {
+            status: Some(BallotStatus::Completed {}),
+            process_state: BallotProcessState::Completed {},
+            private_result: Some(PrivateResult {
+                variable_id,
+                inputs_hash: private_result_inputs_hash(
+                    context.contract_address,
+                    variable_id,
+                    &state.tallied_variables,
+                ),
+                timestamp: context.block_time as u64,
+            }),
+            event_processes: processes,
+            process_counter,
+            tally_started_at: None,
+            failure_reason: None,
+            ..state
+        },
+        events,
+        vec![],
+    )
+}
+
+/// Automatically called when a variable is opened/declassified.
+///
+/// We can now read the sum variable, and compute the average, which will be our final result.
+#[zk_on_variables_opened]
+fn open_tally_result(
+    context: ContractContext,
+    mut state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    opened_variables: Vec<SecretVarId>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        opened_variables.len() == 1,
+        ContractError::UnexpectedOutput,
+        "Unexpected number of output variables"
+    );
+
+    let Some(opened_variable) = opened_variables
+        .first()
+        .and_then(|variable_id| zk_state.get_variable(*variable_id))
+    else {
+        fail(ContractError::MissingVariable, "Opened variable not found")
+    };
+
+    // A vote check without data cannot reject any vote, so the votes are kept
+    if let SecretVarType::VoteCheck { first_vote_id } = opened_variable.metadata {
+        let invalid_mask = opened_variable
+            .data
+            .as_ref()
+            .filter(|data| data.len() == 4)
+            .map_or(0, |data| u32::state_read_from(&mut data.as_slice()));
+        return apply_vote_check(
+            &context,
+            state,
+            &zk_state,
+            opened_variable.variable_id,
+            first_vote_id,
+            invalid_mask,
+        );
+    }
+
+    // A result opened again by a retry is ignored once the ballot has left the tallying status
+    if state.status != Some(BallotStatus::Tallying {}) {
+        return (state, vec![], vec![]);
+    }
+
+    // An opening without data leaves the ballot tallying, so the opening can be retried
+    if opened_variable.data.is_none() {
+        let reason = "Tally result was opened without data".to_string();
+        let process_id = state.process_counter.next_id(context.contract_address);
+        state.event_processes.record_failure(
+            process_id,
+            ProcessState::Failed {},
+            context.block_production_time as u64,
+            reason.clone(),
+        );
+        state.failure_reason = Some(reason);
+        return (state, vec![], vec![]);
+    }
+
+    if let SecretVarType::DrawSeed {} = opened_variable.metadata {
+        let Some(data) = opened_variable.data.as_ref() else {
+            fail(ContractError::MissingVariable, "Draw seed has no data")
+        };
+        let seed = u128::state_read_from(&mut data.as_slice());
+        return complete_draw(&context, state, seed);
+    }
+    if let SecretVarType::BudgetResult {} = opened_variable.metadata {
+        let Some(data) = opened_variable.data.as_ref() else {
+            fail(ContractError::MissingVariable, "Budget result has no data")
+        };
+        let totals = BudgetTotals::state_read_from(&mut data.as_slice());
+        return complete_budget(&context, state, totals);
+    }
+
+    // Use our helper function to read the TallyResult
+    let tally_result = read_variable(&zk_state, &opened_variable.variable_id);
 
     let new_tally = Tally {
         option_0: tally_result.option_0,
@@ -489,72 +2792,919 @@ fn open_tally_result(
             + tally_result.option_4,
     };
 
-    let mut zk_state_changes = vec![];
+    let mut zk_state_changes = vec![];
+    let mut events = vec![];
+
+    if let SecretVarType::TallyResult {} = opened_variable.metadata {
+        // Allocate a process ID for this result
+        let process_id = state.process_counter.next_id(context.contract_address);
+
+        // Update process tracking directly in state
+        let mut processes = state.event_processes.clone();
+        processes.record(
+            process_id.clone(),
+            ProcessState::Complete {},
+            context.block_production_time as u64,
+        );
+
+        let mut ballot_events = vec![
+            BallotEvent::TallyCompleted {
+                timestamp: context.block_time as u64,
+                process_id: process_id.clone(),
+            },
+            BallotEvent::StatusChanged {
+                status: BallotStatus::Completed {},
+                timestamp: context.block_time as u64,
+                process_id: process_id.clone(),
+            },
+        ];
+        // The organization deploys the runoff with the same voter snapshot
+        if let Some(options) = runoff_options(&state, &new_tally) {
+            ballot_events.push(BallotEvent::RunoffRequired {
+                options,
+                voter_root: state.voter_root,
+                voter_count: state.voter_count,
+                timestamp: context.block_time as u64,
+                process_id,
+            });
+        }
+        events = emit_ballot_events(&state, ballot_events);
+
+        // The contract is only done once the result is attested
+        zk_state_changes = vec![ZkStateChange::Attest {
+            data_to_attest: result_attestation_data(
+                context.contract_address,
+                &state.options,
+                &new_tally,
+                BallotStatus::Completed {},
+            ),
+        }];
+
+        state.tally = Some(new_tally);
+        state.added_voters = SortedVecSet::new(); // Reset eligible voter changes
+        state.removed_voters = SortedVecSet::new();
+        state.already_voted = SortedVecSet::new(); // Reset voted list
+        state.status = Some(BallotStatus::Completed {});
+        state.process_state = BallotProcessState::Completed {};
+        state.event_processes = processes;
+        state.tally_started_at = None;
+        state.failure_reason = None;
+    }
+
+    (state, events, zk_state_changes)
+}
+
+/// Automatically called when the ZK nodes have signed the result.
+///
+/// The signatures are stored so the result can be verified on other chains, after which the
+/// contract is done.
+#[zk_on_attestation_complete]
+fn result_attested(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    attestation_id: AttestationId,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let Some(attestation) = zk_state.get_attestation(attestation_id) else {
+        fail(ContractError::MissingVariable, "Attestation not found")
+    };
+
+    (
+        BallotState {
+            result_attestation: Some(ResultAttestation {
+                data: attestation.data.clone(),
+                signatures: attestation.signatures.clone(),
+            }),
+            ..state
+        },
+        vec![],
+        vec![ZkStateChange::ContractDone],
+    )
+}
+
+/// Returns the top two options if the ballot requires a runoff.
+///
+/// A runoff is required when the rule is `TopTwo` and no option has more than half of the
+/// votes. Options with equal votes are ranked in ballot order.
+fn runoff_options(state: &BallotState, tally: &Tally) -> Option<Vec<String>> {
+    if state.runoff_rule == (RunoffRule::None {}) {
+        return None;
+    }
+    let counts = [
+        tally.option_0,
+        tally.option_1,
+        tally.option_2,
+        tally.option_3,
+        tally.option_4,
+    ];
+    let mut ranked: Vec<usize> = (0..state.options.len()).collect();
+    if ranked
+        .iter()
+        .any(|&option| counts[option] as u64 * 2 > tally.total as u64)
+    {
+        return None;
+    }
+    ranked.sort_by(|a, b| counts[*b].cmp(&counts[*a]).then(a.cmp(b)));
+    Some(
+        ranked
+            .iter()
+            .take(2)
+            .map(|&option| state.options[option].clone())
+            .collect(),
+    )
+}
+
+/// Draws the seats from the opened seed and completes the sortition ballot.
+///
+/// Only the positions of the drawn members in the snapshot are known at this point, their
+/// addresses are published with `publish_drawn_member`.
+fn complete_draw(
+    context: &ContractContext,
+    state: BallotState,
+    seed: u128,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let BallotMode::Sortition { seats } = state.ballot_mode else {
+        fail(ContractError::WrongBallotMode, "Ballot is not a sortition");
+    };
+    let selected_indices = draw_indices(context.contract_address, seed, state.voter_count, seats);
+
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::StatusChanged {
+            status: BallotStatus::Completed {},
+            timestamp: context.block_time as u64,
+            process_id,
+        }],
+    );
+
+    (
+        BallotState {
+            status: Some(BallotStatus::Completed {}),
+            process_state: BallotProcessState::Completed {},
+            draw_transcript: Some(DrawTranscript {
+                voter_root: state.voter_root,
+                voter_count: state.voter_count,
+                contributions: state.tallied_variables.clone(),
+                seed,
+                selected: vec![None; selected_indices.len()],
+                selected_indices,
+                timestamp: context.block_time as u64,
+            }),
+            event_processes: processes,
+            process_counter,
+            tally_started_at: None,
+            failure_reason: None,
+            ..state
+        },
+        events,
+        vec![],
+    )
+}
+
+/// Publishes the member drawn for a seat of a completed sortition.
+///
+/// Anyone can resolve the drawn position in the snapshot off-chain and publish the member
+/// there, proving it with a membership proof for that position. Once every seat is published,
+/// the committee is reported and the contract is marked as done.
+#[action(shortname = 0x10, zk = true)]
+fn publish_drawn_member(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    seat: u32,
+    member: Address,
+    membership_proof: Vec<Hash>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let Some(mut transcript) = state.draw_transcript.clone() else {
+        fail(
+            ContractError::InvalidBallotState,
+            "No committee has been drawn",
+        )
+    };
+    let Some(&voter_index) = transcript.selected_indices.get(seat as usize) else {
+        fail(ContractError::InvalidSeatCount, "The draw has no such seat")
+    };
+    require!(
+        transcript.selected[seat as usize].is_none(),
+        ContractError::SeatAlreadyPublished,
+        "The member of seat {} is already published",
+        seat
+    );
+    require!(
+        verify_voter_proof(
+            &transcript.voter_root,
+            &member,
+            voter_index,
+            &membership_proof
+        ),
+        ContractError::InvalidMembershipProof,
+        "The address is not the member at position {} of the snapshot",
+        voter_index
+    );
+    transcript.selected[seat as usize] = Some(member);
+
+    let Some(selected) = transcript
+        .selected
+        .iter()
+        .copied()
+        .collect::<Option<Vec<_>>>()
+    else {
+        return (
+            BallotState {
+                draw_transcript: Some(transcript),
+                ..state
+            },
+            vec![],
+            vec![],
+        );
+    };
+
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        ctx.block_production_time as u64,
+    );
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::CommitteeDrawn {
+            selected,
+            timestamp: ctx.block_time as u64,
+            process_id,
+        }],
+    );
+
+    (
+        BallotState {
+            draw_transcript: Some(transcript),
+            event_processes: processes,
+            process_counter,
+            ..state
+        },
+        events,
+        vec![ZkStateChange::ContractDone],
+    )
+}
+
+/// Records the funded projects and completes the budgeting ballot
+fn complete_budget(
+    context: &ContractContext,
+    state: BallotState,
+    totals: BudgetTotals,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let points: Vec<u32> = [
+        totals.points_0,
+        totals.points_1,
+        totals.points_2,
+        totals.points_3,
+        totals.points_4,
+    ]
+    .into_iter()
+    .take(state.options.len())
+    .collect();
+    let mut ranked: Vec<usize> = (0..state.options.len()).collect();
+    ranked.sort_by(|a, b| points[*b].cmp(&points[*a]).then(a.cmp(b)));
+    let funded: Vec<String> = ranked
+        .into_iter()
+        .filter(|project| totals.funded & (1 << project) != 0)
+        .map(|project| state.options[project].clone())
+        .collect();
+
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(context.contract_address);
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        context.block_production_time as u64,
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![
+            BallotEvent::BudgetAllocated {
+                funded: funded.clone(),
+                spent: totals.spent,
+                timestamp: context.block_time as u64,
+                process_id: process_id.clone(),
+            },
+            BallotEvent::StatusChanged {
+                status: BallotStatus::Completed {},
+                timestamp: context.block_time as u64,
+                process_id,
+            },
+        ],
+    );
+
+    (
+        BallotState {
+            status: Some(BallotStatus::Completed {}),
+            process_state: BallotProcessState::Completed {},
+            budget_outcome: Some(BudgetOutcome {
+                points,
+                funded,
+                spent: totals.spent,
+                timestamp: context.block_time as u64,
+            }),
+            event_processes: processes,
+            process_counter,
+            tally_started_at: None,
+            failure_reason: None,
+            ..state
+        },
+        events,
+        vec![ZkStateChange::ContractDone],
+    )
+}
+
+/// Selects `seats` positions out of `voter_count` with a partial Fisher-Yates shuffle driven by
+/// the seed.
+///
+/// Only the positions moved by a swap are kept, so the cost depends on the seats and not on
+/// the size of the snapshot.
+fn draw_indices(ballot: Address, seed: u128, voter_count: u32, seats: u32) -> Vec<u32> {
+    let mut moved: SortedVecMap<u32, u32> = SortedVecMap::new();
+    let mut selected = Vec::new();
+    for seat in 0..seats.min(voter_count) {
+        let mut bytes: Vec<u8> = Vec::new();
+        WriteRPC::rpc_write_to(&ballot, &mut bytes).unwrap();
+        WriteRPC::rpc_write_to(&seed, &mut bytes).unwrap();
+        WriteRPC::rpc_write_to(&seat, &mut bytes).unwrap();
+        let digest = Hash::digest(&bytes);
+        let mut random_bytes = [0u8; 8];
+        random_bytes.copy_from_slice(&digest.bytes[0..8]);
+        let random = u64::from_be_bytes(random_bytes);
+        let pick = seat + (random % (voter_count - seat) as u64) as u32;
+
+        let at_seat = moved.get(&seat).copied().unwrap_or(seat);
+        let at_pick = moved.get(&pick).copied().unwrap_or(pick);
+        moved.insert(pick, at_seat);
+        selected.push(at_pick);
+    }
+    selected
+}
+
+/// Reads a variable's data as a TallyResult.
+fn read_variable(zk_state: &ZkState<SecretVarType>, variable_id: &SecretVarId) -> TallyResult {
+    let Some(data) = zk_state
+        .get_variable(*variable_id)
+        .and_then(|variable| variable.data.as_ref())
+    else {
+        fail(ContractError::MissingVariable, "Tally result has no data")
+    };
+
+    TallyResult::state_read_from(&mut data.as_slice())
+}
+
+/// Allows the administrator, or a canceller if the ballot has any, to cancel the ballot.
+///
+/// A canceller only proposes the cancellation, which a second canceller or the administrator
+/// confirms with `confirm_cancellation` within `CANCELLATION_WINDOW_MILLIS`. Once cancelled,
+/// all secret votes are deleted from the ZK nodes, the contract is marked as done and the
+/// parent organization is notified.
+#[action(shortname = 0x11, zk = true)]
+fn cancel_ballot(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        !requires_approval(&state),
+        ContractError::ApprovalRequired,
+        "Cancelling requires {} approvals, see approve_action",
+        state.approval_policy.threshold
+    );
+    if state.roles.cancellers.is_empty() {
+        require!(
+            ctx.sender == state.administrator,
+            ContractError::OnlyBallotAdministrator,
+            "Only administrator can cancel ballot"
+        );
+        return cancel(ctx, state, zk_state);
+    }
+
+    require!(
+        is_canceller(&state, &ctx.sender),
+        ContractError::OnlyCanceller,
+        "Only cancellers can propose to cancel the ballot"
+    );
+    require_cancellable(&state);
+    let now = ctx.block_production_time as u64;
+    require!(
+        state
+            .cancellation
+            .as_ref()
+            .is_none_or(|proposal| now > proposal.expires_at),
+        ContractError::AlreadyApproved,
+        "A cancellation is already waiting for confirmation"
+    );
+
+    (
+        BallotState {
+            cancellation: Some(CancellationProposal {
+                proposer: ctx.sender,
+                proposed_at: now,
+                expires_at: now + CANCELLATION_WINDOW_MILLIS,
+            }),
+            ..state
+        },
+        vec![],
+        vec![],
+    )
+}
+
+/// Confirms the cancellation proposed by a canceller.
+///
+/// The confirmation must come from another canceller or the administrator.
+#[action(shortname = 0x0E, zk = true)]
+fn confirm_cancellation(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        !requires_approval(&state),
+        ContractError::ApprovalRequired,
+        "Cancelling requires {} approvals, see approve_action",
+        state.approval_policy.threshold
+    );
+    require!(
+        is_canceller(&state, &ctx.sender) || ctx.sender == state.administrator,
+        ContractError::OnlyCanceller,
+        "Only cancellers or the administrator can confirm a cancellation"
+    );
+    let proposal = state.cancellation.clone().unwrap_or_else(|| {
+        fail(
+            ContractError::NoCancellationProposed,
+            "No cancellation is waiting for confirmation",
+        )
+    });
+    require!(
+        ctx.block_production_time as u64 <= proposal.expires_at,
+        ContractError::NoCancellationProposed,
+        "The proposed cancellation has expired"
+    );
+    require!(
+        proposal.proposer != ctx.sender,
+        ContractError::AlreadyApproved,
+        "A second canceller must confirm the cancellation"
+    );
+
+    cancel(ctx, state, zk_state)
+}
+
+/// Approves an action that needs approval under the approval policy of the ballot.
+///
+/// The first approval proposes the action, which runs as soon as the threshold of approvers
+/// approved it. Approvals lapse when the window since the proposal has passed.
+#[action(shortname = 0x0F, zk = true)]
+fn approve_action(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    action: BallotAction,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        state.approval_policy.approvers.contains(&ctx.sender),
+        ContractError::OnlyApprover,
+        "Only approvers can approve ballot actions"
+    );
+    if action == (BallotAction::Cancel {}) {
+        require_cancellable(&state);
+    }
+
+    let (mut pending_actions, index) = record_approval(
+        &state.pending_actions,
+        &action,
+        ctx.sender,
+        ctx.block_production_time as u64,
+        state.approval_policy.window_seconds,
+    );
+
+    if (pending_actions[index].approvals.len() as u32) < state.approval_policy.threshold {
+        return (
+            BallotState {
+                pending_actions,
+                ..state
+            },
+            vec![],
+            vec![],
+        );
+    }
+
+    pending_actions.remove(index);
+    let state = BallotState {
+        pending_actions,
+        ..state
+    };
+    match action {
+        BallotAction::Cancel {} => cancel(ctx, state, zk_state),
+        BallotAction::SyncVoters {
+            voter_root,
+            voter_count,
+        } => {
+            let (state, events) = apply_voter_sync(&ctx, state, voter_root, voter_count, true);
+            (state, events, vec![])
+        }
+    }
+}
+
+/// Fails unless the ballot can still be cancelled
+fn require_cancellable(state: &BallotState) {
+    require!(
+        state.status != Some(BallotStatus::Completed {}),
+        ContractError::BallotEnded,
+        "Cannot cancel completed ballot"
+    );
+    require!(
+        state.status != Some(BallotStatus::TallyFailed {}),
+        ContractError::BallotEnded,
+        "Cannot cancel failed ballot"
+    );
+    require!(
+        state.status != Some(BallotStatus::Cancelled {}),
+        ContractError::BallotEnded,
+        "Ballot is already cancelled"
+    );
+}
+
+/// Cancels the ballot, deleting all secret votes and marking the contract as done
+fn cancel(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    require!(
+        zk_state.calculation_state == CalculationStatus::Waiting
+            || vote_check_stalled(&state, ctx.block_production_time as u64),
+        ContractError::ComputationInProgress,
+        "Cannot cancel ballot while a computation is in flight, but was {:?}",
+        zk_state.calculation_state
+    );
+    require_cancellable(&state);
+
+    // Allocate a process ID for this cancellation
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
+
+    // Set the new status
+    let new_status = BallotStatus::Cancelled {};
+
+    // Update process tracking directly in state instead of emitting self-event
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        ctx.block_production_time as u64,
+    );
+
+    // Secret votes must not outlive the ballot
+    let votes_to_delete = vote_variable_ids(&zk_state);
+
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::StatusChanged {
+            status: new_status,
+            timestamp: ctx.block_time as u64,
+            process_id,
+        }],
+    );
+
+    (
+        BallotState {
+            status: Some(new_status),
+            process_state: BallotProcessState::Cancelled {},
+            event_processes: processes,
+            process_counter,
+            cancellation: None,
+            ..state
+        },
+        events,
+        vec![
+            ZkStateChange::DeleteVariables {
+                variables_to_delete: votes_to_delete,
+            },
+            ZkStateChange::ContractDone,
+        ],
+    )
+}
+
+/// Allows the administrator to pause voting, e.g. to correct the ballot mid-vote.
+///
+/// While paused, `cast_vote` rejects inputs and the administrator can correct the ballot
+/// metadata and eligible voters.
+#[action(shortname = 0x07, zk = true)]
+fn pause_ballot(
+    ctx: ContractContext,
+    state: BallotState,
+    _zk_state: ZkState<SecretVarType>,
+) -> (BallotState, Vec<EventGroup>) {
+    require!(
+        ctx.sender == state.administrator,
+        ContractError::OnlyBallotAdministrator,
+        "Only administrator can pause ballot"
+    );
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
+    );
+
+    // Allocate a process ID for this pause
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
+
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        ctx.block_production_time as u64,
+    );
+
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::StatusChanged {
+            status: BallotStatus::Paused {},
+            timestamp: ctx.block_time as u64,
+            process_id,
+        }],
+    );
+
+    (
+        BallotState {
+            status: Some(BallotStatus::Paused {}),
+            process_state: BallotProcessState::Paused {},
+            event_processes: processes,
+            process_counter,
+            paused_at: Some(ctx.block_production_time as u64),
+            ..state
+        },
+        events,
+    )
+}
+
+/// Allows the administrator to resume voting on a paused ballot.
+///
+/// If `extend_end_time` is set, the end of the voting period is moved by the time the ballot
+/// was paused.
+#[action(shortname = 0x08, zk = true)]
+fn resume_ballot(
+    ctx: ContractContext,
+    state: BallotState,
+    _zk_state: ZkState<SecretVarType>,
+    extend_end_time: bool,
+) -> (BallotState, Vec<EventGroup>) {
+    require!(
+        ctx.sender == state.administrator,
+        ContractError::OnlyBallotAdministrator,
+        "Only administrator can resume ballot"
+    );
+    require!(
+        state.status == Some(BallotStatus::Paused {}),
+        ContractError::BallotNotPaused,
+        "Ballot is not paused"
+    );
+
+    let Some(paused_at) = state.paused_at else {
+        fail(
+            ContractError::InvalidBallotState,
+            "Paused ballot has no pause time",
+        )
+    };
+    let paused_duration = ctx.block_production_time as u64 - paused_at;
+    let end_time = if extend_end_time {
+        state.end_time + paused_duration
+    } else {
+        state.end_time
+    };
+
+    // Allocate a process ID for this resumption
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
 
-    if let SecretVarType::TallyResult {} = opened_variable.metadata {
-        // Generate a process ID for this result
-        let process_id = generate_process_id(&context);
+    let mut processes = state.event_processes.clone();
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        ctx.block_production_time as u64,
+    );
 
-        // Update process tracking directly in state
-        let mut processes = state.event_processes.clone();
-        processes.insert(process_id.clone(), ProcessState::Complete {});
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::StatusChanged {
+            status: BallotStatus::Active {},
+            timestamp: ctx.block_time as u64,
+            process_id,
+        }],
+    );
 
-        state.tally = Some(new_tally);
-        state.eligible_voters = Vec::new(); // Reset eligible voters
-        state.already_voted = Vec::new(); // Reset voted list
-        state.status = Some(BallotStatus::Completed {});
-        state.process_state = BallotProcessState::Completed {};
-        state.event_processes = processes;
-        zk_state_changes = vec![ZkStateChange::ContractDone]
+    (
+        BallotState {
+            status: Some(BallotStatus::Active {}),
+            process_state: BallotProcessState::Active {},
+            event_processes: processes,
+            process_counter,
+            end_time,
+            paused_at: None,
+            ..state
+        },
+        events,
+    )
+}
+
+/// Allows the administrator to amend the title, description and options of the ballot.
+///
+/// Everything can be amended until the first vote is cast, in any participation or ballot
+/// mode. After that, only the title and description of a paused ballot can be corrected.
+/// Every amendment is recorded as a new revision in `metadata_revisions`.
+///
+/// The options of a budgeting ballot are its projects, so `costs` must give the cost of every
+/// project whenever they change. Other ballots take no costs.
+#[action(shortname = 0x09, zk = true)]
+fn update_ballot_metadata(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    title: Option<String>,
+    description: Option<String>,
+    options: Option<Vec<String>>,
+    costs: Option<Vec<u32>>,
+) -> (BallotState, Vec<EventGroup>) {
+    require!(
+        ctx.sender == state.administrator,
+        ContractError::OnlyBallotAdministrator,
+        "Only administrator can update ballot metadata"
+    );
+    require!(
+        matches!(
+            state.status,
+            Some(BallotStatus::Active {}) | Some(BallotStatus::Paused {})
+        ),
+        ContractError::InvalidBallotState,
+        "Cannot update metadata in current ballot state"
+    );
+    if participation_started(&state, &zk_state) {
+        require!(
+            state.status == Some(BallotStatus::Paused {}),
+            ContractError::MetadataLocked,
+            "Ballot must be paused to update metadata after votes are cast"
+        );
+        require!(
+            options.is_none() && costs.is_none(),
+            ContractError::MetadataLocked,
+            "Options cannot be changed after votes are cast"
+        );
     }
 
-    (state, vec![], zk_state_changes)
-}
+    if let Some(ref title_val) = title {
+        require!(
+            !title_val.is_empty(),
+            ContractError::EmptyValue,
+            "Ballot title cannot be empty"
+        );
+    }
 
-/// Reads a variable's data as a TallyResult.
-fn read_variable(zk_state: &ZkState<SecretVarType>, variable_id: &SecretVarId) -> TallyResult {
-    let variable = zk_state.get_variable(*variable_id).unwrap();
-    let buffer: Vec<u8> = variable.data.clone().unwrap();
+    if let Some(ref description_val) = description {
+        require!(
+            !description_val.is_empty(),
+            ContractError::EmptyValue,
+            "Ballot description cannot be empty"
+        );
+    }
+
+    if let Some(ref options_val) = options {
+        require!(
+            options_val.len() <= 5,
+            ContractError::InvalidOptionCount,
+            "At most 5 options are supported"
+        );
+        require!(
+            options_val.len() > 1,
+            ContractError::InvalidOptionCount,
+            "At least 2 options are required"
+        );
+    }
+
+    let ballot_mode = match state.ballot_mode.clone() {
+        BallotMode::Budgeting {
+            budget,
+            costs: current_costs,
+        } => {
+            require!(
+                options.is_none() || costs.is_some(),
+                ContractError::InvalidBudget,
+                "Changing the projects requires their costs"
+            );
+            let costs = costs.unwrap_or(current_costs);
+            let project_count = options.as_ref().map_or(state.options.len(), Vec::len);
+            validate_budget(budget, &costs, project_count);
+            BallotMode::Budgeting { budget, costs }
+        }
+        ballot_mode => {
+            require!(
+                costs.is_none(),
+                ContractError::WrongBallotMode,
+                "Only budgeting ballots have project costs"
+            );
+            ballot_mode
+        }
+    };
+
+    let mut new_state = BallotState {
+        title: title.unwrap_or(state.title),
+        description: description.unwrap_or(state.description),
+        options: options.unwrap_or(state.options),
+        ballot_mode,
+        ..state
+    };
+    let revision = metadata_revision(
+        new_state.title.clone(),
+        new_state.description.clone(),
+        new_state.options.clone(),
+        ctx.block_production_time as u64,
+    );
+    new_state.metadata_revisions.push(revision);
 
-    TallyResult::state_read_from(&mut buffer.as_slice())
+    (new_state, vec![])
 }
 
-#[action(shortname = 0x11, zk = true)]
-fn cancel_ballot(
+/// Queries the sender's token balance for a token-gated ballot.
+///
+/// Calls `balance_at` on the configured token with the ballot's start time as snapshot, the
+/// result is cached by `token_balance_callback`. Must be called once before the first vote.
+#[action(shortname = 0x0A, zk = true)]
+fn check_token_eligibility(
     ctx: ContractContext,
     state: BallotState,
     _zk_state: ZkState<SecretVarType>,
 ) -> (BallotState, Vec<EventGroup>) {
-    assert_eq!(
-        ctx.sender, state.administrator,
-        "Only administrator can cancel ballot"
+    let Eligibility::TokenBalance { token, .. } = state.eligibility else {
+        fail(ContractError::NotTokenGated, "Ballot is not token gated");
+    };
+    require!(
+        state.status == Some(BallotStatus::Active {}),
+        ContractError::BallotNotActive,
+        "Ballot is not active"
     );
-
-    assert!(
-        state.status.unwrap() != BallotStatus::Completed {},
-        "Cannot cancel completed ballot"
+    require!(
+        state.token_balances.get(&ctx.sender).is_none(),
+        ContractError::TokenBalanceKnown,
+        "Token balance is already known"
     );
 
-    // Generate a process ID for this cancellation
-    let process_id = generate_process_id(&ctx);
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(token, TOKEN_BALANCE_AT_SHORTNAME)
+        .argument(ctx.sender)
+        .argument(state.start_time)
+        .done();
+    event_group
+        .with_callback(SHORTNAME_TOKEN_BALANCE_CALLBACK)
+        .with_cost(10000)
+        .argument(ctx.sender)
+        .done();
 
-    // Set the new status
-    let new_status = BallotStatus::Cancelled {};
+    (state, vec![event_group.build()])
+}
 
-    // Update process tracking directly in state instead of emitting self-event
-    let mut processes = state.event_processes.clone();
-    processes.insert(process_id.clone(), ProcessState::Complete {});
+/// Caches the token balance returned by the token contract.
+#[callback(shortname = 0x21, zk = true)]
+fn token_balance_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    state: BallotState,
+    _zk_state: ZkState<SecretVarType>,
+    voter: Address,
+) -> (BallotState, Vec<EventGroup>) {
+    require!(
+        callback_ctx.success,
+        ContractError::TokenQueryFailed,
+        "Token balance query failed"
+    );
 
-    // No events emitted to self - we're directly updating state
+    let return_data = &callback_ctx.results[0].return_data;
+    let balance = u128::rpc_read_from(&mut return_data.as_slice());
+
+    let mut token_balances = state.token_balances.clone();
+    token_balances.insert(voter, balance);
 
     (
         BallotState {
-            status: Some(new_status),
-            process_state: BallotProcessState::Cancelled {},
-            event_processes: processes,
+            token_balances,
             ..state
         },
-        vec![], // No events
+        vec![],
     )
 }
 
@@ -572,7 +3722,11 @@ fn status_changed(
         } => {
             // Track the process
             let mut processes = state.event_processes.clone();
-            processes.insert(process_id, ProcessState::Complete {});
+            processes.record(
+                process_id,
+                ProcessState::Complete {},
+                ctx.block_production_time as u64,
+            );
 
             (
                 BallotState {
@@ -585,7 +3739,11 @@ fn status_changed(
         BallotEvent::MembersUpdated { process_id, .. } => {
             // Track the process for member updates
             let mut processes = state.event_processes.clone();
-            processes.insert(process_id, ProcessState::Complete {});
+            processes.record(
+                process_id,
+                ProcessState::Complete {},
+                ctx.block_production_time as u64,
+            );
 
             (
                 BallotState {
@@ -601,38 +3759,652 @@ fn status_changed(
 
 /// Allows the administrator to manually sync the eligible voters list with the organization
 /// This is a workaround for ZK contract limitations with automatic event broadcasting
+///
+/// The new snapshot replaces the old one, discarding any changes recorded since. Active ballots
+/// can only be synced if deployed with `MembershipPolicy::LiveSync`, paused ballots can always
+/// be corrected by the administrator. If the approval policy requires approvals, the
+/// administrator syncs through `approve_action` instead, and a sync by the organization is only
+/// proposed as a pending action that the approvers confirm with `approve_action`.
 #[action(shortname = 0x05, zk = true)]
 fn sync_voters(
     ctx: ContractContext,
     state: BallotState,
     _zk_state: ZkState<SecretVarType>,
-    new_eligible_voters: Vec<Address>,
+    new_voter_root: Hash,
+    new_voter_count: u32,
 ) -> (BallotState, Vec<EventGroup>) {
     // Only the administrator or the organization contract can update voters
-    assert!(
+    require!(
         ctx.sender == state.administrator || ctx.sender == state.organization,
+        ContractError::OnlyBallotAdministratorOrOrganization,
         "Only administrator or organization can sync voters"
     );
+    if requires_approval(&state) {
+        require!(
+            ctx.sender == state.organization,
+            ContractError::ApprovalRequired,
+            "Syncing voters requires {} approvals, see approve_action",
+            state.approval_policy.threshold
+        );
+        let (pending_actions, _) = propose_action(
+            &state.pending_actions,
+            &BallotAction::SyncVoters {
+                voter_root: new_voter_root,
+                voter_count: new_voter_count,
+            },
+            ctx.block_production_time as u64,
+            state.approval_policy.window_seconds,
+        );
+        return (
+            BallotState {
+                pending_actions,
+                ..state
+            },
+            vec![],
+        );
+    }
+
+    let by_administrator = ctx.sender == state.administrator;
+    apply_voter_sync(
+        &ctx,
+        state,
+        new_voter_root,
+        new_voter_count,
+        by_administrator,
+    )
+}
 
-    // Only allow syncing if the ballot is active
-    assert!(
-        state.status.unwrap() == BallotStatus::Active {},
+/// Replaces the voter snapshot of the ballot.
+///
+/// `by_administrator` is set when the administrator, or the approvers, sync the voters, which
+/// allows correcting paused ballots regardless of their membership policy.
+fn apply_voter_sync(
+    ctx: &ContractContext,
+    state: BallotState,
+    new_voter_root: Hash,
+    new_voter_count: u32,
+    by_administrator: bool,
+) -> (BallotState, Vec<EventGroup>) {
+    // Only allow syncing if the ballot is active or paused
+    let paused = state.status == Some(BallotStatus::Paused {});
+    require!(
+        state.status == Some(BallotStatus::Active {}) || paused,
+        ContractError::InvalidBallotState,
         "Cannot sync voters in current ballot state"
     );
+    require!(
+        state.membership_policy == MembershipPolicy::LiveSync {} || (paused && by_administrator),
+        ContractError::MembershipPolicyViolation,
+        "Voters can only be synced on ballots with live membership sync"
+    );
 
-    // Generate a process ID for this sync
-    let process_id = generate_process_id(&ctx);
+    // Allocate a process ID for this sync
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
 
     // Track the process
     let mut processes = state.event_processes.clone();
-    processes.insert(process_id.clone(), ProcessState::Complete {});
+    processes.record(
+        process_id.clone(),
+        ProcessState::Complete {},
+        ctx.block_production_time as u64,
+    );
+
+    // The snapshot is replaced as a whole, so no individual addresses are reported
+    let events = emit_ballot_events(
+        &state,
+        vec![BallotEvent::MembersUpdated {
+            added: vec![],
+            removed: vec![],
+            timestamp: ctx.block_time as u64,
+            process_id,
+        }],
+    );
+
+    (
+        BallotState {
+            voter_root: new_voter_root,
+            voter_count: new_voter_count,
+            added_voters: SortedVecSet::new(),
+            removed_voters: SortedVecSet::new(),
+            event_processes: processes,
+            process_counter,
+            ..state
+        },
+        events,
+    )
+}
+
+/// Allows the administrator to set an additional receiver of ballot events.
+///
+/// The listener receives every `BallotEvent` through an action with shortname 0x41, in the
+/// same event group as the parent organization. `None` removes the listener.
+#[action(shortname = 0x06, zk = true)]
+fn set_event_listener(
+    ctx: ContractContext,
+    state: BallotState,
+    _zk_state: ZkState<SecretVarType>,
+    event_listener: Option<Address>,
+) -> (BallotState, Vec<EventGroup>) {
+    require!(
+        ctx.sender == state.administrator,
+        ContractError::OnlyBallotAdministrator,
+        "Only administrator can set the event listener"
+    );
+    require!(
+        event_listener != Some(state.organization),
+        ContractError::InvalidAddress,
+        "The organization already receives ballot events"
+    );
+
+    (
+        BallotState {
+            event_listener,
+            ..state
+        },
+        vec![],
+    )
+}
+
+/// Allows the administrator to prune finished process records older than the given age.
+///
+/// Pruned records are only kept as counts in `event_processes`.
+#[action(shortname = 0x0B, zk = true)]
+fn prune_processes(
+    ctx: ContractContext,
+    state: BallotState,
+    _zk_state: ZkState<SecretVarType>,
+    max_age_seconds: u64,
+) -> (BallotState, Vec<EventGroup>) {
+    require!(
+        ctx.sender == state.administrator,
+        ContractError::OnlyBallotAdministrator,
+        "Only administrator can prune processes"
+    );
+
+    let cutoff = (ctx.block_production_time as u64).saturating_sub(max_age_seconds * 1000);
+    let mut processes = state.event_processes.clone();
+    processes.prune_older_than(cutoff);
 
     (
         BallotState {
-            eligible_voters: new_eligible_voters,
             event_processes: processes,
             ..state
         },
-        vec![], // No events
+        vec![],
+    )
+}
+
+/// Decides whether the contract may be upgraded.
+///
+/// Only the administrator can upgrade a ballot, and only before the first vote is cast, so
+/// secret votes never cross a change of the contract code.
+#[upgrade_is_allowed]
+fn is_upgrade_allowed(
+    ctx: ContractContext,
+    state: BallotState,
+    _old_contract_hashes: ContractHashes,
+    _new_contract_hashes: ContractHashes,
+    _new_contract_rpc: Vec<u8>,
+) -> bool {
+    ctx.sender == state.administrator
+        && state.vote_receipts.is_empty()
+        && state.administrator_shares == 0
+        && state.approver_shares == 0
+        && state.anonymous_votes == 0
+        && state.draw_contributors.is_empty()
+}
+
+/// Migrates the state of the previous contract version to the current layout.
+///
+/// Version 1 is the first versioned layout, so there is nothing to migrate yet and a state of
+/// version 1 is read as `BallotState` unchanged. Ballots deployed before states were versioned
+/// have a different layout and cannot be upgraded. When the layout changes, bump
+/// `STATE_VERSION`, keep the replaced struct as `BallotStateV<n>` and convert it field by field
+/// here.
+#[upgrade]
+fn upgrade(ctx: ContractContext, state: BallotState) -> BallotState {
+    require!(
+        state.state_version <= STATE_VERSION,
+        ContractError::UnsupportedStateVersion,
+        "Cannot migrate state version {} to {}",
+        state.state_version,
+        STATE_VERSION
+    );
+
+    BallotState {
+        state_version: STATE_VERSION,
+        ..state
+    }
+}
+
+/// Allows a recipient of a private result or an observer to request their own copy of it.
+///
+/// The result is copied under MPC and the copy is transferred to the sender, who can then
+/// reconstruct it off-chain from the shares held by the ZK nodes. Voters prove their
+/// eligibility the same way as when casting a vote.
+#[action(shortname = 0x0C, zk = true)]
+fn request_result(
+    ctx: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    voter_index: u32,
+    membership_proof: Vec<Hash>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let private_result = state.private_result.clone().unwrap_or_else(|| {
+        fail(
+            ContractError::NoPrivateResult,
+            "Ballot has no private result",
+        )
+    });
+    let allowed = state.roles.observers.contains(&ctx.sender)
+        || match &state.result_disclosure {
+            ResultDisclosure::Public {} => false,
+            ResultDisclosure::Voters {} => {
+                is_eligible(&state, &ctx.sender, voter_index, &membership_proof)
+            }
+            ResultDisclosure::Administrators { administrators } => {
+                administrators.contains(&ctx.sender)
+            }
+        };
+    require!(
+        allowed,
+        ContractError::NotResultRecipient,
+        "Not allowed to receive the result"
+    );
+    require!(
+        !state.result_recipients.contains(&ctx.sender),
+        ContractError::ResultAlreadyDisclosed,
+        "Result was already disclosed to the sender"
+    );
+    require!(
+        zk_state.calculation_state == CalculationStatus::Waiting,
+        ContractError::ComputationInProgress,
+        "Another copy of the result is being computed, but was {:?}",
+        zk_state.calculation_state
+    );
+
+    (
+        BallotState {
+            pending_recipient: Some(ctx.sender),
+            ..state
+        },
+        vec![],
+        vec![zk_compute::copy_tally_result_start(
+            SecretVarId::new(private_result.variable_id),
+            Some(SHORTNAME_RESULT_COPIED),
+            &[SecretVarType::ResultCopy {}],
+        )],
+    )
+}
+
+/// Automatically called when a copy of the private result has been computed.
+///
+/// The copy is transferred to the recipient that requested it.
+#[zk_on_compute_complete(shortname = 0x63)]
+fn result_copied(
+    context: ContractContext,
+    state: BallotState,
+    zk_state: ZkState<SecretVarType>,
+    output_variables: Vec<SecretVarId>,
+) -> (BallotState, Vec<EventGroup>, Vec<ZkStateChange>) {
+    let Some(recipient) = state.pending_recipient else {
+        fail(
+            ContractError::InvalidBallotState,
+            "No result copy is pending",
+        )
+    };
+
+    // Without output the recipient can request the result again
+    let mut result_recipients = state.result_recipients.clone();
+    if !output_variables.is_empty() {
+        result_recipients.insert(recipient);
+    }
+
+    (
+        BallotState {
+            pending_recipient: None,
+            result_recipients,
+            ..state
+        },
+        vec![],
+        output_variables
+            .into_iter()
+            .map(|variable| ZkStateChange::TransferVariable {
+                variable,
+                new_owner: recipient,
+            })
+            .collect(),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pbc_contract_common::address::AddressType;
+
+    fn account(id: u8) -> Address {
+        Address {
+            address_type: AddressType::Account,
+            identifier: [id; 20],
+        }
+    }
+
+    #[test]
+    fn voter_proof_of_every_snapshot_member_verifies() {
+        let (a, b, c) = (account(1), account(2), account(3));
+        let ab = voter_node(&voter_leaf(0, &a), &voter_leaf(1, &b));
+        let root = voter_node(&ab, &voter_leaf(2, &c));
+
+        assert!(verify_voter_proof(
+            &root,
+            &a,
+            0,
+            &[voter_leaf(1, &b), voter_leaf(2, &c)]
+        ));
+        assert!(verify_voter_proof(
+            &root,
+            &b,
+            1,
+            &[voter_leaf(0, &a), voter_leaf(2, &c)]
+        ));
+        // The unpaired leaf is carried up, so its proof skips that level
+        assert!(verify_voter_proof(&root, &c, 2, &[ab]));
+    }
+
+    #[test]
+    fn voter_proof_of_other_address_fails() {
+        let (a, b, c) = (account(1), account(2), account(3));
+        let root = voter_node(&voter_leaf(0, &a), &voter_leaf(1, &b));
+
+        assert!(!verify_voter_proof(&root, &c, 0, &[voter_leaf(1, &b)]));
+        assert!(!verify_voter_proof(&root, &a, 0, &[voter_leaf(0, &c)]));
+        assert!(!verify_voter_proof(&root, &a, 0, &[]));
+    }
+
+    #[test]
+    fn voter_proof_is_bound_to_position() {
+        let (a, b) = (account(1), account(2));
+        let root = voter_node(&voter_leaf(0, &a), &voter_leaf(1, &b));
+
+        assert!(verify_voter_proof(&root, &b, 1, &[voter_leaf(0, &a)]));
+        assert!(!verify_voter_proof(&root, &b, 0, &[voter_leaf(0, &a)]));
+    }
+
+    #[test]
+    fn drawn_indices_are_distinct_positions_in_snapshot() {
+        let indices = draw_indices(account(7), 42, 10, 10);
+
+        let mut sorted = indices.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..10).collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn drawn_indices_cover_large_snapshots() {
+        let indices = draw_indices(account(7), 42, 100_000, 5);
+
+        assert_eq!(indices.len(), 5);
+        assert!(indices.iter().all(|index| *index < 100_000));
+        let mut sorted = indices.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted.len(), 5);
+    }
+
+    #[test]
+    fn drawn_indices_depend_on_seed_only() {
+        assert_eq!(
+            draw_indices(account(7), 42, 1_000, 3),
+            draw_indices(account(7), 42, 1_000, 3)
+        );
+        assert_ne!(
+            draw_indices(account(7), 42, 1_000, 3),
+            draw_indices(account(7), 43, 1_000, 3)
+        );
+    }
+
+    fn options(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn metadata_revision_keeps_metadata_and_time() {
+        let revision = metadata_revision(
+            "Budget".to_string(),
+            "Next year".to_string(),
+            options(&["Yes", "No"]),
+            1_000,
+        );
+        assert_eq!(revision.title, "Budget");
+        assert_eq!(revision.description, "Next year");
+        assert_eq!(revision.options, options(&["Yes", "No"]));
+        assert_eq!(revision.timestamp, 1_000);
+    }
+
+    #[test]
+    fn metadata_revision_hash_covers_metadata_only() {
+        let revision = |title: &str, option_names: &[&str], timestamp| {
+            metadata_revision(
+                title.to_string(),
+                String::new(),
+                options(option_names),
+                timestamp,
+            )
+            .content_hash
+            .bytes
+        };
+        let original = revision("Budget", &["Yes", "No"], 1_000);
+
+        assert_eq!(original, revision("Budget", &["Yes", "No"], 2_000));
+        assert_ne!(original, revision("Budgets", &["Yes", "No"], 1_000));
+        assert_ne!(original, revision("Budget", &["No", "Yes"], 1_000));
+        // Fields are length prefixed, so moving text between them changes the hash
+        assert_ne!(original, revision("Budget", &["YesNo"], 1_000));
+    }
+
+    #[test]
+    fn budget_with_costs_within_budget_is_valid() {
+        validate_budget(100, &[1, 50, 100], 3);
+        validate_budget(MAX_BUDGET, &[MAX_BUDGET], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SEKIVA-209]")]
+    fn empty_budget_is_invalid() {
+        validate_budget(0, &[], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SEKIVA-209]")]
+    fn budget_above_maximum_is_invalid() {
+        validate_budget(MAX_BUDGET + 1, &[1], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SEKIVA-209]")]
+    fn budget_needs_cost_per_project() {
+        validate_budget(100, &[10, 20], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SEKIVA-209]")]
+    fn free_project_is_invalid() {
+        validate_budget(100, &[10, 0], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SEKIVA-209]")]
+    fn project_above_budget_is_invalid() {
+        validate_budget(100, &[10, 101], 2);
+    }
+
+    #[test]
+    fn first_approval_proposes_action() {
+        let (pending, index) = record_approval(&[], &BallotAction::Cancel {}, account(1), 0, 60);
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[index].action, BallotAction::Cancel {});
+        assert_eq!(pending[index].approvals, vec![account(1)]);
+        assert_eq!(pending[index].expires_at, 60_000);
+    }
+
+    #[test]
+    fn approvals_of_same_action_are_counted_together() {
+        let sync = BallotAction::SyncVoters {
+            voter_root: voter_leaf(0, &account(9)),
+            voter_count: 3,
+        };
+        let (pending, _) = record_approval(&[], &BallotAction::Cancel {}, account(1), 0, 60);
+        let (pending, _) = record_approval(&pending, &sync, account(1), 0, 60);
+        let (pending, index) =
+            record_approval(&pending, &BallotAction::Cancel {}, account(2), 0, 60);
+
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[index].approvals, vec![account(1), account(2)]);
+        // The window runs from the proposal, later approvals do not extend it
+        assert_eq!(pending[index].expires_at, 60_000);
+    }
+
+    #[test]
+    fn lapsed_approvals_are_dropped() {
+        let (pending, _) = record_approval(&[], &BallotAction::Cancel {}, account(1), 0, 60);
+        let (pending, index) =
+            record_approval(&pending, &BallotAction::Cancel {}, account(2), 60_001, 60);
+
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[index].approvals, vec![account(2)]);
+        assert_eq!(pending[index].expires_at, 120_001);
+    }
+
+    #[test]
+    fn proposed_sync_needs_every_approval() {
+        let sync = BallotAction::SyncVoters {
+            voter_root: voter_leaf(0, &account(9)),
+            voter_count: 3,
+        };
+        let (pending, index) = propose_action(&[], &sync, 0, 60);
+
+        assert!(pending[index].approvals.is_empty());
+        assert_eq!(pending[index].expires_at, 60_000);
+
+        // Proposing the same sync again does not reset its approvals
+        let (pending, _) = record_approval(&pending, &sync, account(1), 0, 60);
+        let (pending, index) = propose_action(&pending, &sync, 0, 60);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[index].approvals, vec![account(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "[SEKIVA-309]")]
+    fn approving_twice_fails() {
+        let (pending, _) = record_approval(&[], &BallotAction::Cancel {}, account(1), 0, 60);
+        record_approval(&pending, &BallotAction::Cancel {}, account(1), 0, 60);
+    }
+
+    /// The votes the fold computation adds for a batch, all votes from its first to last id
+    fn folded_by_computation(vote_ids: &[u32], batch: &[u32]) -> Vec<u32> {
+        let (Some(first), Some(last)) = (batch.first(), batch.last()) else {
+            return vec![];
+        };
+        let mut folded: Vec<u32> = vote_ids
+            .iter()
+            .copied()
+            .filter(|vote_id| first <= vote_id && vote_id <= last)
+            .collect();
+        folded.sort_unstable();
+        folded
+    }
+
+    #[test]
+    fn fold_batch_takes_checked_votes_in_id_order() {
+        let vote_ids = [8, 3, 5];
+        let batch = fold_batch(&vote_ids, &[]);
+
+        assert_eq!(batch, vec![3, 5, 8]);
+        assert_eq!(folded_by_computation(&vote_ids, &batch), batch);
+    }
+
+    #[test]
+    fn fold_batch_stops_at_pending_check() {
+        let vote_ids = [3, 5, 8, 9, 12];
+        let batch = fold_batch(&vote_ids, &[8]);
+
+        assert_eq!(batch, vec![3, 5]);
+        assert_eq!(folded_by_computation(&vote_ids, &batch), batch);
+    }
+
+    #[test]
+    fn fold_batch_is_empty_while_oldest_vote_is_checked() {
+        assert!(fold_batch(&[3, 5, 8], &[3]).is_empty());
+    }
+
+    #[test]
+    fn fold_batch_stops_at_unconfirmed_vote() {
+        // Vote 4 is not confirmed yet, so it is not among the vote ids but still unchecked
+        let vote_ids = [3, 5, 8];
+        let batch = fold_batch(&vote_ids, &[4]);
+
+        assert_eq!(batch, vec![3]);
+        assert_eq!(folded_by_computation(&vote_ids, &batch), batch);
+    }
+
+    #[test]
+    fn fold_batch_is_capped() {
+        let vote_ids: Vec<u32> = (0..FOLD_BATCH_SIZE as u32 + 10).collect();
+        let batch = fold_batch(&vote_ids, &[]);
+
+        assert_eq!(batch.len(), FOLD_BATCH_SIZE);
+        assert_eq!(folded_by_computation(&vote_ids, &batch), batch);
+    }
+
+    #[test]
+    fn completed_fold_deletes_exactly_the_folded_votes() {
+        let vote_ids = [3, 5, 8, 9, 12];
+        let batch = fold_batch(&vote_ids, &[9]);
+        let (folded, deleted) = complete_fold(&batch, &[1, 2], Some(7));
+
+        assert_eq!(folded, vec![1, 2, 3, 5, 8]);
+        let deleted: Vec<u32> = deleted.iter().map(|variable| variable.raw_id).collect();
+        assert_eq!(deleted, vec![3, 5, 8, 7]);
+        // The vote whose check is pending is neither folded nor deleted, so the tally counts
+        // it once
+        assert!(!folded.contains(&9) && !deleted.contains(&9));
+        assert!(!folded_by_computation(&vote_ids, &batch).contains(&9));
+    }
+
+    #[test]
+    fn check_batch_spans_check_batch_size_ids() {
+        let pending = [40, 7, 9, 7 + CHECK_BATCH_SIZE];
+        let (batch, pending) = check_batch(&pending, None);
+
+        assert_eq!(batch, vec![7, 9]);
+        assert_eq!(pending, vec![40, 7 + CHECK_BATCH_SIZE]);
+    }
+
+    #[test]
+    fn check_batch_stops_at_unconfirmed_vote() {
+        // Vote 9 is not confirmed yet, so vote 12 must wait for it to be checked
+        let (batch, pending) = check_batch(&[12, 7, 8], Some(9));
+
+        assert_eq!(batch, vec![7, 8]);
+        assert_eq!(pending, vec![12]);
+    }
+
+    #[test]
+    fn check_batch_is_empty_below_unconfirmed_vote() {
+        let (batch, pending) = check_batch(&[12, 7], Some(5));
+
+        assert!(batch.is_empty());
+        assert_eq!(pending, vec![12, 7]);
+    }
+
+    #[test]
+    fn voter_node_ignores_sibling_order() {
+        let (left, right) = (voter_leaf(0, &account(1)), voter_leaf(1, &account(2)));
+        assert_eq!(
+            voter_node(&left, &right).bytes,
+            voter_node(&right, &left).bytes
+        );
+    }
+}
@@ -3,6 +3,55 @@ use pbc_zk::*;
 
 #[allow(unused)]
 const VOTE_VARIABLE_KIND: u8 = 0u8;
+#[allow(unused)]
+const ADMINISTRATOR_SHARE_KIND: u8 = 3u8;
+#[allow(unused)]
+const ANONYMOUS_VOTE_KIND: u8 = 4u8;
+#[allow(unused)]
+const DRAW_CONTRIBUTION_KIND: u8 = 5u8;
+#[allow(unused)]
+const BUDGET_ALLOCATION_KIND: u8 = 7u8;
+#[allow(unused)]
+const PARTIAL_TALLY_KIND: u8 = 9u8;
+#[allow(unused)]
+const APPROVER_SHARE_KIND: u8 = 11u8;
+
+/// Points each voter distributes across the projects of a budgeting ballot
+pub const MAX_ALLOCATION_POINTS: i32 = 100;
+
+/// Secret input of an anonymous vote: the two shares of the voter's one-time token and the
+/// chosen option
+#[derive(CreateTypeSpec, SecretBinary)]
+pub struct AnonymousBallot {
+    administrator_share: Sbi128,
+    approver_share: Sbi128,
+    option: Sbi8,
+}
+
+/// Secret input of a budgeting ballot: the points a voter allocates to each project.
+///
+/// An allocation only counts if no project gets negative points, projects that do not exist
+/// get none, and the points add up to at most `MAX_ALLOCATION_POINTS`.
+#[derive(CreateTypeSpec, SecretBinary)]
+pub struct BudgetAllocation {
+    project_0: Sbi32,
+    project_1: Sbi32,
+    project_2: Sbi32,
+    project_3: Sbi32,
+    project_4: Sbi32,
+}
+
+/// Result of a budgeting ballot, `funded` has bit `i` set if project `i` is funded
+#[derive(CreateTypeSpec, SecretBinary)]
+pub struct BudgetOutput {
+    points_0: Sbi32,
+    points_1: Sbi32,
+    points_2: Sbi32,
+    points_3: Sbi32,
+    points_4: Sbi32,
+    funded: Sbi32,
+    spent: Sbi32,
+}
 
 #[derive(CreateTypeSpec, SecretBinary)]
 pub struct TallyOutput {
@@ -15,7 +64,41 @@ pub struct TallyOutput {
 
 #[zk_compute(shortname = 0x72)]
 pub fn tally_votes() -> TallyOutput {
-    // Initialize counters for each option
+    // Start from the votes folded so far, if any
+    let mut tally = partial_tally();
+
+    // Count the remaining votes for each option
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == VOTE_VARIABLE_KIND {
+            tally = count_vote(tally, load_sbi::<Sbi8>(variable_id));
+        }
+    }
+
+    tally
+}
+
+/// Folds the votes with ids from `first_vote_id` to `last_vote_id` into a new running total.
+///
+/// The running total is the sum of the previous running total and the folded votes, so the
+/// folded votes can be deleted afterwards.
+#[zk_compute(shortname = 0x77)]
+pub fn fold_votes(first_vote_id: u32, last_vote_id: u32) -> TallyOutput {
+    let mut tally = partial_tally();
+
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == VOTE_VARIABLE_KIND
+            && variable_id.raw_id >= first_vote_id
+            && variable_id.raw_id <= last_vote_id
+        {
+            tally = count_vote(tally, load_sbi::<Sbi8>(variable_id));
+        }
+    }
+
+    tally
+}
+
+/// The running total of the folded votes, zero if no votes were folded yet
+fn partial_tally() -> TallyOutput {
     let mut tally = TallyOutput {
         option_0: Sbi32::from(0),
         option_1: Sbi32::from(0),
@@ -24,25 +107,273 @@ pub fn tally_votes() -> TallyOutput {
         option_4: Sbi32::from(0),
     };
 
-    // Count votes for each option
     for variable_id in secret_variable_ids() {
-        if load_metadata::<u8>(variable_id) == VOTE_VARIABLE_KIND {
-            let vote_option = load_sbi::<Sbi8>(variable_id);
+        if load_metadata::<u8>(variable_id) == PARTIAL_TALLY_KIND {
+            let partial = load_sbi::<TallyOutput>(variable_id);
+            tally.option_0 = tally.option_0 + partial.option_0;
+            tally.option_1 = tally.option_1 + partial.option_1;
+            tally.option_2 = tally.option_2 + partial.option_2;
+            tally.option_3 = tally.option_3 + partial.option_3;
+            tally.option_4 = tally.option_4 + partial.option_4;
+        }
+    }
 
-            // Increment the appropriate counter
-            if vote_option == Sbi8::from(0) {
-                tally.option_0 = tally.option_0 + Sbi32::from(1);
-            } else if vote_option == Sbi8::from(1) {
-                tally.option_1 = tally.option_1 + Sbi32::from(1);
-            } else if vote_option == Sbi8::from(2) {
-                tally.option_2 = tally.option_2 + Sbi32::from(1);
-            } else if vote_option == Sbi8::from(3) {
-                tally.option_3 = tally.option_3 + Sbi32::from(1);
-            } else if vote_option == Sbi8::from(4) {
-                tally.option_4 = tally.option_4 + Sbi32::from(1);
-            }
+    tally
+}
+
+/// Adds a single vote to the counter of its option
+fn count_vote(mut tally: TallyOutput, vote_option: Sbi8) -> TallyOutput {
+    if vote_option == Sbi8::from(0) {
+        tally.option_0 = tally.option_0 + Sbi32::from(1);
+    } else if vote_option == Sbi8::from(1) {
+        tally.option_1 = tally.option_1 + Sbi32::from(1);
+    } else if vote_option == Sbi8::from(2) {
+        tally.option_2 = tally.option_2 + Sbi32::from(1);
+    } else if vote_option == Sbi8::from(3) {
+        tally.option_3 = tally.option_3 + Sbi32::from(1);
+    } else if vote_option == Sbi8::from(4) {
+        tally.option_4 = tally.option_4 + Sbi32::from(1);
+    }
+    tally
+}
+
+/// Copies the tally result, so the copy can be handed to a single recipient
+#[zk_compute(shortname = 0x73)]
+pub fn copy_tally_result(result_id: SecretVarId) -> TallyOutput {
+    load_sbi::<TallyOutput>(result_id)
+}
+
+/// Tallies anonymous votes.
+///
+/// Every anonymous vote passed `check_anonymous_votes` before the tally starts, which deleted
+/// the votes with a token that was not issued or was used already, so each remaining vote
+/// counts.
+#[zk_compute(shortname = 0x74)]
+pub fn tally_anonymous_votes() -> TallyOutput {
+    let mut tally = partial_tally();
+
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == ANONYMOUS_VOTE_KIND {
+            let vote = load_sbi::<AnonymousBallot>(variable_id);
+            tally = count_vote(tally, vote.option);
         }
     }
 
     tally
 }
+
+/// Combines the secret randomness contributed by the candidates of a draw into a seed.
+///
+/// The seed is random as long as a single contribution is, and no contributor learns it
+/// before all contributions are locked in.
+#[zk_compute(shortname = 0x75)]
+pub fn combine_randomness() -> Sbi128 {
+    let mut seed = Sbi128::from(0);
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == DRAW_CONTRIBUTION_KIND {
+            seed = seed + load_sbi::<Sbi128>(variable_id);
+        }
+    }
+    seed
+}
+
+/// Aggregates the budget allocations and funds projects greedily within the budget.
+///
+/// Projects are visited from most to fewest points, ties in project order, and funded if
+/// their cost still fits in what is left of the budget. A cost of 0 marks a project that does
+/// not exist. Only the points, the funded projects and the amount spent are revealed.
+#[zk_compute(shortname = 0x76)]
+pub fn allocate_budget(
+    budget: u32,
+    cost_0: u32,
+    cost_1: u32,
+    cost_2: u32,
+    cost_3: u32,
+    cost_4: u32,
+) -> BudgetOutput {
+    let costs = [cost_0, cost_1, cost_2, cost_3, cost_4];
+    let mut points = [Sbi32::from(0); 5];
+
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == BUDGET_ALLOCATION_KIND {
+            let allocation = load_sbi::<BudgetAllocation>(variable_id);
+            let shares = [
+                allocation.project_0,
+                allocation.project_1,
+                allocation.project_2,
+                allocation.project_3,
+                allocation.project_4,
+            ];
+
+            let mut valid = Sbi8::from(1);
+            let mut total = Sbi32::from(0);
+            for project in 0..5 {
+                if shares[project] < Sbi32::from(0) {
+                    valid = Sbi8::from(0);
+                }
+                if shares[project] > Sbi32::from(MAX_ALLOCATION_POINTS) {
+                    valid = Sbi8::from(0);
+                }
+                if costs[project] == 0 {
+                    if shares[project] != Sbi32::from(0) {
+                        valid = Sbi8::from(0);
+                    }
+                }
+                total = total + shares[project];
+            }
+            if total > Sbi32::from(MAX_ALLOCATION_POINTS) {
+                valid = Sbi8::from(0);
+            }
+
+            if valid == Sbi8::from(1) {
+                for project in 0..5 {
+                    points[project] = points[project] + shares[project];
+                }
+            }
+        }
+    }
+
+    // The rank of a project is the number of projects visited before it
+    let mut ranks = [Sbi32::from(0); 5];
+    for project in 0..5 {
+        for other in 0..5 {
+            if costs[other] != 0 && other != project {
+                if other < project {
+                    if points[other] >= points[project] {
+                        ranks[project] = ranks[project] + Sbi32::from(1);
+                    }
+                } else if points[other] > points[project] {
+                    ranks[project] = ranks[project] + Sbi32::from(1);
+                }
+            }
+        }
+    }
+
+    let mut funded = Sbi32::from(0);
+    let mut spent = Sbi32::from(0);
+    for rank in 0..5 {
+        for project in 0..5 {
+            if costs[project] != 0 {
+                if ranks[project] == Sbi32::from(rank as i32) {
+                    let cost = Sbi32::from(costs[project] as i32);
+                    if spent + cost <= Sbi32::from(budget as i32) {
+                        spent = spent + cost;
+                        funded = funded + Sbi32::from(1 << project);
+                    }
+                }
+            }
+        }
+    }
+
+    BudgetOutput {
+        points_0: points[0],
+        points_1: points[1],
+        points_2: points[2],
+        points_3: points[3],
+        points_4: points[4],
+        funded,
+        spent,
+    }
+}
+
+/// Checks that the votes with ids from `first_vote_id` to `first_vote_id + 31` are one of the
+/// `option_count` options of the ballot.
+///
+/// Only the mask of invalid votes is opened, bit `i` is set if vote `first_vote_id + i` is out
+/// of range.
+#[zk_compute(shortname = 0x78)]
+pub fn check_votes(first_vote_id: u32, option_count: u32) -> Sbi32 {
+    let mut invalid = Sbi32::from(0);
+    for variable_id in secret_variable_ids() {
+        if load_metadata::<u8>(variable_id) == VOTE_VARIABLE_KIND
+            && variable_id.raw_id >= first_vote_id
+            && variable_id.raw_id - first_vote_id < 32
+        {
+            let bit = Sbi32::from((1u32 << (variable_id.raw_id - first_vote_id)) as i32);
+            let vote = load_sbi::<Sbi8>(variable_id);
+            if vote < Sbi8::from(0) {
+                invalid = invalid + bit;
+            }
+            if vote >= Sbi8::from(option_count as i8) {
+                invalid = invalid + bit;
+            }
+        }
+    }
+    invalid
+}
+
+/// Checks the anonymous votes with ids from `first_vote_id` to `last_vote_id`, at most 32.
+///
+/// A vote is valid if both shares of its token were issued, its option is one of the
+/// `option_count` options, and no valid vote with a lower id used either share. The votes with
+/// lower ids outside the range passed their check already. Only the mask of invalid votes is
+/// opened, bit `i` is set if vote `first_vote_id + i` is invalid, so the tokens stay secret.
+#[zk_compute(shortname = 0x79)]
+pub fn check_anonymous_votes(first_vote_id: u32, last_vote_id: u32, option_count: u32) -> Sbi32 {
+    let mut invalid = Sbi32::from(0);
+    // Validity of the votes of the range checked so far, variables are visited in id order
+    let mut valid_in_range = [Sbi8::from(0); 32];
+
+    for vote_id in secret_variable_ids() {
+        if load_metadata::<u8>(vote_id) == ANONYMOUS_VOTE_KIND
+            && vote_id.raw_id >= first_vote_id
+            && vote_id.raw_id <= last_vote_id
+        {
+            let offset = (vote_id.raw_id - first_vote_id) as usize;
+            let vote = load_sbi::<AnonymousBallot>(vote_id);
+            let mut administrator_issued = Sbi8::from(0);
+            let mut approver_issued = Sbi8::from(0);
+            let mut used = Sbi8::from(0);
+
+            for other_id in secret_variable_ids() {
+                let kind = load_metadata::<u8>(other_id);
+                if kind == ADMINISTRATOR_SHARE_KIND {
+                    if load_sbi::<Sbi128>(other_id) == vote.administrator_share {
+                        administrator_issued = Sbi8::from(1);
+                    }
+                } else if kind == APPROVER_SHARE_KIND {
+                    if load_sbi::<Sbi128>(other_id) == vote.approver_share {
+                        approver_issued = Sbi8::from(1);
+                    }
+                } else if kind == ANONYMOUS_VOTE_KIND && other_id.raw_id < vote_id.raw_id {
+                    let other = load_sbi::<AnonymousBallot>(other_id);
+                    let mut counted = Sbi8::from(1);
+                    if other_id.raw_id >= first_vote_id {
+                        counted = valid_in_range[(other_id.raw_id - first_vote_id) as usize];
+                    }
+                    if counted == Sbi8::from(1) {
+                        if other.administrator_share == vote.administrator_share {
+                            used = Sbi8::from(1);
+                        }
+                        if other.approver_share == vote.approver_share {
+                            used = Sbi8::from(1);
+                        }
+                    }
+                }
+            }
+
+            let mut valid = Sbi8::from(1);
+            if administrator_issued == Sbi8::from(0) {
+                valid = Sbi8::from(0);
+            }
+            if approver_issued == Sbi8::from(0) {
+                valid = Sbi8::from(0);
+            }
+            if used == Sbi8::from(1) {
+                valid = Sbi8::from(0);
+            }
+            if vote.option < Sbi8::from(0) {
+                valid = Sbi8::from(0);
+            }
+            if vote.option >= Sbi8::from(option_count as i8) {
+                valid = Sbi8::from(0);
+            }
+
+            valid_in_range[offset] = valid;
+            if valid == Sbi8::from(0) {
+                invalid = invalid + Sbi32::from((1u32 << offset) as i32);
+            }
+        }
+    }
+    invalid
+}
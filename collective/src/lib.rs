@@ -10,9 +10,12 @@ use pbc_contract_common::address::{Address, AddressType, Shortname};
 use pbc_contract_common::context::{CallbackContext, ContractContext};
 use pbc_contract_common::events::EventGroup;
 use pbc_contract_common::sorted_vec_map::{SortedVecMap, SortedVecSet};
+use pbc_contract_common::upgrade::ContractHashes;
+use pbc_contract_common::Hash;
 use pbc_traits::WriteRPC;
 use read_write_rpc_derive::ReadWriteRPC;
 use read_write_state_derive::ReadWriteState;
+use sekiva_common::{fail, require, ContractError, ProcessCounter};
 
 // https://browser.testnet.partisiablockchain.com/contracts/018bc1ccbb672b87710327713c97d43204905082cb/deployContractV3
 const DEPLOY_ZK_CONTRACT_ADDRESS: Address = Address {
@@ -22,11 +25,22 @@ const DEPLOY_ZK_CONTRACT_ADDRESS: Address = Address {
         0x04, 0x90, 0x50, 0x82, 0xcb,
     ],
 };
+
+/// Version of the `OrganizationState` layout, bumped whenever the layout changes
+const STATE_VERSION: u32 = 1;
+
+/// Longest membership tenure a ballot can require, about ten years
+const MAX_TENURE_DAYS: u32 = 3650;
+
 const DEPLOY_ZK_SHORTNAME: Shortname = Shortname::from_u32(2);
 const ZK_BINDER_ID: i32 = 11;
 const BALLOT_DEPLOYED_SHORTNAME: Shortname = Shortname::from_u32(0x40);
 const BALLOT_DEPLOY_FAILED_SHORTNAME: Shortname = Shortname::from_u32(0x43);
 const HANDLE_ORG_EVENT_SHORTNAME: Shortname = Shortname::from_u32(0x11);
+const BALLOT_HANDLE_ORG_EVENT_SHORTNAME: Shortname = Shortname::from_u32(0x30);
+const BALLOT_SYNC_VOTERS_SHORTNAME: Shortname = Shortname::from_u32(0x05);
+/// Shortname of `balance_at(owner, timestamp)` of the checkpoint token interface
+const TOKEN_BALANCE_AT_SHORTNAME: Shortname = Shortname::from_u32(0x20);
 
 #[derive(CreateTypeSpec, ReadWriteState, Debug, PartialEq, Clone, Copy)]
 #[repr(u8)]
@@ -45,20 +59,244 @@ enum BallotProcessState {
     Cancelled {},
 }
 
+impl BallotProcessState {
+    /// Whether the process has ended, so its record may be pruned
+    fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            BallotProcessState::Active {}
+                | BallotProcessState::Completed {}
+                | BallotProcessState::Cancelled {}
+        )
+    }
+}
+
+/// Tracking record of a single process
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct BallotProcessRecord {
+    state: BallotProcessState,
+    timestamp: u64,
+    failure_reason: Option<String>, // Why the process failed
+}
+
+/// Bounded log of processes.
+///
+/// Finished records are pruned once the log exceeds its maximum size, or on request by age.
+/// Pruned records are only kept as counts.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct BallotProcessLog {
+    records: SortedVecMap<String, BallotProcessRecord>,
+    pruned_count: u64,    // Number of records pruned so far
+    pruned_failures: u64, // Number of pruned records that had a failure reason
+}
+
+/// Mirrors `BallotStatus` of the ballot contract, discriminants must match.
 #[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
 #[repr(u8)]
-
 enum BallotStatus {
     #[discriminant(0)]
-    Created {},
-    #[discriminant(1)]
     Active {},
-    #[discriminant(2)]
+    #[discriminant(1)]
     Tallying {},
-    #[discriminant(3)]
+    #[discriminant(2)]
     Completed {},
-    #[discriminant(4)]
+    #[discriminant(3)]
     Cancelled {},
+    #[discriminant(4)]
+    TallyFailed {},
+    #[discriminant(5)]
+    Paused {},
+}
+
+/// Events reported by ballots, mirrors `BallotEvent` of the ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+#[repr(u8)]
+enum BallotEvent {
+    #[discriminant(0)]
+    MembersUpdated {
+        added: Vec<Address>,
+        removed: Vec<Address>,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(1)]
+    VoteCast {
+        voter: Address,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(2)]
+    TallyStarted { timestamp: u64, process_id: String },
+    #[discriminant(3)]
+    TallyCompleted { timestamp: u64, process_id: String },
+    #[discriminant(4)]
+    StatusChanged {
+        status: BallotStatus,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(5)]
+    CommitteeDrawn {
+        selected: Vec<Address>,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(6)]
+    RunoffRequired {
+        options: Vec<String>,
+        voter_root: Hash,
+        voter_count: u32,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(7)]
+    BudgetAllocated {
+        funded: Vec<String>,
+        spent: u32,
+        timestamp: u64,
+        process_id: String,
+    },
+    #[discriminant(8)]
+    VoteRejected {
+        voter: Address,
+        timestamp: u64,
+        process_id: String,
+    },
+}
+
+/// How a running ballot follows membership changes, mirrors `MembershipPolicy` of the ballot
+/// contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone, Copy)]
+#[repr(u8)]
+enum MembershipPolicy {
+    /// Eligible voters are fixed at deployment
+    #[discriminant(0)]
+    Snapshot {},
+    /// New members become eligible, removed members stay eligible
+    #[discriminant(1)]
+    AddOnly {},
+    /// Eligible voters follow the organization
+    #[discriminant(2)]
+    LiveSync {},
+}
+
+/// An off-chain proposal document, mirrors `Attachment` of the ballot contract.
+///
+/// `sha256` is the lowercase hex encoded SHA-256 hash of the document.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+struct Attachment {
+    uri: String,
+    sha256: String,
+    mime_type: String,
+}
+
+/// Who may vote on a ballot, mirrors `Eligibility` of the ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone, Copy)]
+#[repr(u8)]
+enum Eligibility {
+    /// Members of the organization
+    #[discriminant(0)]
+    Membership {},
+    /// Holders of at least `min_balance` of `token` when the ballot starts
+    #[discriminant(1)]
+    TokenBalance { token: Address, min_balance: u128 },
+}
+
+/// What a ballot decides, mirrors `BallotMode` of the ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+#[repr(u8)]
+enum BallotMode {
+    /// Voters choose between the options
+    #[discriminant(0)]
+    Vote {},
+    /// `seats` members are drawn by lot from the voter snapshot
+    #[discriminant(1)]
+    Sortition { seats: u32 },
+    /// Voters allocate points to the options as projects, which are funded by points within
+    /// `budget`. `costs` holds the cost of each option
+    #[discriminant(2)]
+    Budgeting { budget: u32, costs: Vec<u32> },
+}
+
+/// What happens when no option of a completed ballot has a majority, mirrors `RunoffRule` of
+/// the ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone, Copy)]
+#[repr(u8)]
+enum RunoffRule {
+    /// The option with the most votes wins
+    #[discriminant(0)]
+    None {},
+    /// A runoff between the top two options is deployed, running for `duration_seconds`
+    #[discriminant(1)]
+    TopTwo { duration_seconds: u64 },
+}
+
+/// Addresses with a role on a ballot besides its administrator, mirrors `BallotRoles` of the
+/// ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+struct BallotRoles {
+    talliers: Vec<Address>,   // May start, fold, retry and abandon the tally
+    cancellers: Vec<Address>, // May propose a cancellation, confirmed by a second one
+    observers: Vec<Address>,  // May request a copy of a private result
+}
+
+/// Approval policy of a ballot, mirrors `ApprovalPolicy` of the ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+struct ApprovalPolicy {
+    approvers: Vec<Address>,
+    threshold: u32,
+    window_seconds: u64,
+}
+
+/// How many administrators must approve cancelling a ballot and syncing its voters, and within
+/// how long. A `threshold` of 0 or 1 lets the ballot administrator act alone.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone, Copy)]
+struct ApprovalSettings {
+    threshold: u32,
+    window_seconds: u64,
+}
+
+/// Settings of a ballot that are carried over to its runoff
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct RunoffTemplate {
+    title: String,
+    description: String,
+    administrator: Address,
+    duration_seconds: u64,
+    attachments: Vec<Attachment>,
+    eligibility: Eligibility,
+    participation_mode: ParticipationMode,
+    roles: BallotRoles,
+    approvals: ApprovalSettings,
+}
+
+/// Whether the addresses taking part in a ballot are public, mirrors `ParticipationMode` of the
+/// ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone, Copy)]
+#[repr(u8)]
+enum ParticipationMode {
+    /// Voters vote from their own address
+    #[discriminant(0)]
+    Public {},
+    /// Voters are issued a secret one-time token during the first `issuance_seconds` of the
+    /// ballot, and vote with it from any address once issuance has closed
+    #[discriminant(1)]
+    Anonymous { issuance_seconds: u64 },
+}
+
+/// Who gets to see the result of a ballot, mirrors `ResultDisclosure` of the ballot contract.
+#[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
+#[repr(u8)]
+enum ResultDisclosure {
+    /// The result is opened on-chain for everyone
+    #[discriminant(0)]
+    Public {},
+    /// The result stays secret, eligible voters can request their own copy
+    #[discriminant(1)]
+    Voters {},
+    /// The result stays secret, the given administrators can request their own copy
+    #[discriminant(2)]
+    Administrators { administrators: Vec<Address> },
 }
 
 #[derive(CreateTypeSpec, ReadWriteState, ReadWriteRPC, Clone)]
@@ -100,9 +338,11 @@ enum OrganizationEvent {
 /// The state of the petition, which is persisted on-chain.
 #[state]
 pub struct OrganizationState {
+    state_version: u32, // Layout version of this state, see `STATE_VERSION`
     owner: Address,
     administrators: SortedVecSet<Address>, // can make changes to the org, members and add proposals
     members: SortedVecSet<Address>,
+    member_since: SortedVecMap<Address, u64>, // Block production time each member joined at
     name: String,
     description: String,
     profile_image: String,
@@ -113,21 +353,164 @@ pub struct OrganizationState {
     ballot_contract_zkwa: Vec<u8>,
     ballot_contract_abi: Vec<u8>,
     ballots: SortedVecSet<Address>,
-    event_nonce: u64, // Track event nonce for security
-    ballot_processes: SortedVecMap<String, BallotProcessState>, // Track ballot processes by process_id
-    factory_address: Address, // Address of the factory that created this collective
+    event_nonce: u64,                   // Track event nonce for security
+    ballot_processes: BallotProcessLog, // Track ballot processes by process_id
+    factory_address: Address,           // Address of the factory that created this collective
+    ballot_statuses: SortedVecMap<Address, BallotStatus>, // Last status reported by each ballot
+    process_counter: ProcessCounter,    // Allocates process IDs
+    upgrader: Option<Address>,          // Who may upgrade the contract, `None` disables upgrades
+    runoff_templates: SortedVecMap<Address, RunoffTemplate>, // Ballots that may need a runoff
+    runoff_parents: SortedVecMap<Address, Address>, // Runoff ballot to the ballot it follows up
 }
 
 // UI link example:
 // https://propostr.xyz/0x1234567890123456789012345678901234567890/p/0x1234567890123456789012345678901234567890
 
-/// Generates a unique process ID using timestamp and transaction bytes
-fn generate_process_id(ctx: &ContractContext) -> String {
-    let bytes_hex = ctx.original_transaction.bytes[0..8]
+/// Hashes a voter address and its position in the snapshot into a leaf of the voter Merkle tree
+fn voter_leaf(index: u32, voter: &Address) -> Hash {
+    let mut bytes: Vec<u8> = vec![0x00];
+    WriteRPC::rpc_write_to(&index, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(voter, &mut bytes).unwrap();
+    Hash::digest(&bytes)
+}
+
+/// Hashes two sibling nodes of the voter Merkle tree, ordered by value
+fn voter_node(left: &Hash, right: &Hash) -> Hash {
+    let (first, second) = if left.bytes <= right.bytes {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    let mut bytes: Vec<u8> = vec![0x01];
+    bytes.extend_from_slice(&first.bytes);
+    bytes.extend_from_slice(&second.bytes);
+    Hash::digest(&bytes)
+}
+
+/// Selects the members that joined at least `min_tenure_days` days before `now`, in member order.
+///
+/// Every member gets a join time when added, a member without one never qualifies.
+fn tenured_members(
+    members: &SortedVecSet<Address>,
+    member_since: &SortedVecMap<Address, u64>,
+    now: u64,
+    min_tenure_days: u32,
+) -> Vec<Address> {
+    let joined_by = now.saturating_sub(min_tenure_days as u64 * 86_400_000);
+    members
+        .iter()
+        .filter(|member| {
+            member_since
+                .get(*member)
+                .is_some_and(|joined_at| *joined_at <= joined_by)
+        })
+        .copied()
+        .collect()
+}
+
+/// Computes the Merkle root of the voter snapshot handed to a ballot.
+///
+/// Leaves are built from the voters and their positions in the given order, an unpaired node is
+/// carried up to the next level unchanged. Must stay in sync with proof verification in the ballot contract.
+fn voter_merkle_root(voters: &[Address]) -> Hash {
+    let mut level: Vec<Hash> = voters
         .iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<String>();
-    format!("{}-{}", ctx.block_time, bytes_hex)
+        .enumerate()
+        .map(|(index, voter)| voter_leaf(index as u32, voter))
+        .collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => voter_node(left, right),
+                _ => pair[0].clone(),
+            })
+            .collect();
+    }
+    level
+        .pop()
+        .expect("Cannot build a voter snapshot without voters.")
+}
+
+/// Maximum number of process records kept before the oldest finished ones are pruned.
+const MAX_TRACKED_PROCESSES: usize = 100;
+
+impl BallotProcessLog {
+    fn new() -> Self {
+        BallotProcessLog {
+            records: SortedVecMap::new(),
+            pruned_count: 0,
+            pruned_failures: 0,
+        }
+    }
+
+    /// Records the state of a process
+    fn record(&mut self, process_id: String, state: BallotProcessState, timestamp: u64) {
+        self.insert(
+            process_id,
+            BallotProcessRecord {
+                state,
+                timestamp,
+                failure_reason: None,
+            },
+        );
+    }
+
+    /// Records the state of a process together with why it failed
+    fn record_failure(
+        &mut self,
+        process_id: String,
+        state: BallotProcessState,
+        timestamp: u64,
+        reason: String,
+    ) {
+        self.insert(
+            process_id,
+            BallotProcessRecord {
+                state,
+                timestamp,
+                failure_reason: Some(reason),
+            },
+        );
+    }
+
+    fn insert(&mut self, process_id: String, record: BallotProcessRecord) {
+        self.records.insert(process_id, record);
+        while self.records.len() > MAX_TRACKED_PROCESSES {
+            let oldest = self
+                .records
+                .iter()
+                .filter(|(_, record)| record.state.is_finished())
+                .min_by_key(|(_, record)| record.timestamp)
+                .map(|(process_id, _)| process_id.clone());
+            match oldest {
+                Some(process_id) => self.archive(process_id),
+                None => break,
+            }
+        }
+    }
+
+    /// Prunes all finished records older than the cutoff
+    fn prune_older_than(&mut self, cutoff: u64) {
+        let expired: Vec<String> = self
+            .records
+            .iter()
+            .filter(|(_, record)| record.state.is_finished() && record.timestamp < cutoff)
+            .map(|(process_id, _)| process_id.clone())
+            .collect();
+        for process_id in expired {
+            self.archive(process_id);
+        }
+    }
+
+    fn archive(&mut self, process_id: String) {
+        if let Some(record) = self.records.remove(&process_id) {
+            self.pruned_count += 1;
+            if record.failure_reason.is_some() {
+                self.pruned_failures += 1;
+            }
+        }
+    }
 }
 
 /// Initialize a new organization.
@@ -166,24 +549,45 @@ pub fn initialize(
     ballot_contract_abi: Vec<u8>,
     factory_address: Address,
 ) -> OrganizationState {
-    assert_ne!(name, "", "Please name the organization.");
-    assert_ne!(description, "", "Please describe the organization.");
-    assert_ne!(
-        administrator, ctx.sender,
+    require!(
+        !name.is_empty(),
+        ContractError::EmptyValue,
+        "Please name the organization."
+    );
+    require!(
+        !description.is_empty(),
+        ContractError::EmptyValue,
+        "Please describe the organization."
+    );
+    require!(
+        administrator != ctx.sender,
+        ContractError::InvalidAddress,
         "Administrator cannot be the factory."
     );
-    assert_ne!(ballot_contract_zkwa, vec![], "Ballot binary is missing.");
-    assert_ne!(ballot_contract_abi, vec![], "Ballot abi is missing.");
+    require!(
+        !ballot_contract_zkwa.is_empty(),
+        ContractError::EmptyValue,
+        "Ballot binary is missing."
+    );
+    require!(
+        !ballot_contract_abi.is_empty(),
+        ContractError::EmptyValue,
+        "Ballot abi is missing."
+    );
 
     let mut members = SortedVecSet::new();
     members.insert(administrator);
     let mut administrators = SortedVecSet::new();
     administrators.insert(administrator);
+    let mut member_since = SortedVecMap::new();
+    member_since.insert(administrator, ctx.block_production_time as u64);
 
     OrganizationState {
+        state_version: STATE_VERSION,
         owner: administrator,
         administrators,
         members,
+        member_since,
         ballots: SortedVecSet::new(),
         name,
         description,
@@ -194,9 +598,14 @@ pub fn initialize(
         discord_server,
         ballot_contract_zkwa,
         ballot_contract_abi,
-        event_nonce: 0,                        // Initialize event nonce
-        ballot_processes: SortedVecMap::new(), // Initialize empty ballot process tracking
+        event_nonce: 0,                            // Initialize event nonce
+        ballot_processes: BallotProcessLog::new(), // Initialize empty ballot process tracking
         factory_address,
+        ballot_statuses: SortedVecMap::new(),
+        process_counter: ProcessCounter::new(),
+        upgrader: Some(administrator),
+        runoff_templates: SortedVecMap::new(),
+        runoff_parents: SortedVecMap::new(),
     }
 }
 
@@ -218,16 +627,19 @@ pub fn add_administrator(
     state: OrganizationState,
     address: Address,
 ) -> OrganizationState {
-    assert_eq!(
-        state.owner, ctx.sender,
+    require!(
+        state.owner == ctx.sender,
+        ContractError::OnlyOwner,
         "Only the owner can add an administrator."
     );
-    assert!(
+    require!(
         !state.administrators.contains(&address),
+        ContractError::AlreadyAdministrator,
         "Already an administrator."
     );
-    assert!(
+    require!(
         state.members.contains(&address),
+        ContractError::NotMember,
         "Please add the address as a member first."
     );
 
@@ -270,40 +682,66 @@ pub fn update_metadata(
     x_account: Option<String>,
     discord_server: Option<String>,
 ) -> OrganizationState {
-    assert!(
+    require!(
         state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
         "Only administrators can update organization metadata."
     );
 
     if let Some(ref name_val) = name {
-        assert_ne!(name_val, "", "Organization name cannot be empty.");
+        require!(
+            !name_val.is_empty(),
+            ContractError::EmptyValue,
+            "Organization name cannot be empty."
+        );
     }
 
     if let Some(ref description_val) = description {
-        assert_ne!(
-            description_val, "",
+        require!(
+            !description_val.is_empty(),
+            ContractError::EmptyValue,
             "Organization description cannot be empty."
         );
     }
 
     if let Some(ref profile_image_val) = profile_image {
-        assert_ne!(profile_image_val, "", "Profile image URL cannot be empty.");
+        require!(
+            !profile_image_val.is_empty(),
+            ContractError::EmptyValue,
+            "Profile image URL cannot be empty."
+        );
     }
 
     if let Some(ref banner_image_val) = banner_image {
-        assert_ne!(banner_image_val, "", "Banner image URL cannot be empty.");
+        require!(
+            !banner_image_val.is_empty(),
+            ContractError::EmptyValue,
+            "Banner image URL cannot be empty."
+        );
     }
 
     if let Some(ref website_val) = website {
-        assert_ne!(website_val, "", "Website URL cannot be empty.");
+        require!(
+            !website_val.is_empty(),
+            ContractError::EmptyValue,
+            "Website URL cannot be empty."
+        );
     }
 
     if let Some(ref x_account_val) = x_account {
-        assert_ne!(x_account_val, "", "X account cannot be empty.");
+        require!(
+            !x_account_val.is_empty(),
+            ContractError::EmptyValue,
+            "X account cannot be empty."
+        );
     }
 
     if let Some(ref discord_server_val) = discord_server {
-        assert_ne!(discord_server_val, "", "Discord server cannot be empty.");
+        require!(
+            !discord_server_val.is_empty(),
+            ContractError::EmptyValue,
+            "Discord server cannot be empty."
+        );
     }
 
     // Apply updates only for fields that were provided
@@ -337,19 +775,26 @@ pub fn remove_administrator(
     state: OrganizationState,
     address: Address,
 ) -> OrganizationState {
-    assert_eq!(
-        state.owner, ctx.sender,
+    require!(
+        state.owner == ctx.sender,
+        ContractError::OnlyOwner,
         "Only the owner can remove an administrator."
     );
-    assert!(
+    require!(
         state.administrators.contains(&address),
+        ContractError::NotAdministrator,
         "Could not remove non-administrator."
     );
-    assert!(
+    require!(
         state.members.contains(&address),
+        ContractError::NotMember,
         "Could not remove non-member."
     );
-    assert!(state.owner != address, "Cannot remove the owner.");
+    require!(
+        state.owner != address,
+        ContractError::CannotRemoveOwner,
+        "Cannot remove the owner."
+    );
 
     let mut administrators = state.administrators.clone();
     administrators.remove(&address);
@@ -378,41 +823,50 @@ pub fn add_member(
     state: OrganizationState,
     address: Address,
 ) -> (OrganizationState, Vec<EventGroup>) {
-    assert!(
+    require!(
         state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
         "Only admins can add a member."
     );
-    assert!(!state.members.contains(&address), "Already a member.");
+    require!(
+        !state.members.contains(&address),
+        ContractError::AlreadyMember,
+        "Already a member."
+    );
 
     let mut members = state.members.clone();
     members.insert(address);
+    let mut member_since = state.member_since.clone();
+    member_since.insert(address, ctx.block_production_time as u64);
 
-    // Generate a process ID
-    let process_id = generate_process_id(&ctx);
+    // Allocate a process ID
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
 
     // Increment event nonce
     let event_nonce = state.event_nonce + 1;
 
-    // Create event for the factory
-    let mut event_group = EventGroup::builder();
-    event_group
-        .call(state.factory_address, HANDLE_ORG_EVENT_SHORTNAME)
-        .argument(OrganizationEvent::MembersAdded {
+    // Create event for the factory and the running ballots
+    let event_group = membership_event_group(
+        &state,
+        OrganizationEvent::MembersAdded {
             members: vec![address],
             organization: ctx.contract_address,
             timestamp: ctx.block_time as u64,
-            process_id: process_id.clone(),
+            process_id,
             nonce: event_nonce,
-        })
-        .done();
+        },
+    );
 
     (
         OrganizationState {
             members,
+            member_since,
+            process_counter,
             event_nonce,
             ..state
         },
-        vec![event_group.build()],
+        vec![event_group],
     )
 }
 
@@ -434,48 +888,53 @@ pub fn add_members(
     state: OrganizationState,
     addresses: Vec<Address>,
 ) -> (OrganizationState, Vec<EventGroup>) {
-    assert!(
+    require!(
         state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
         "Only admins can add members."
     );
 
     let mut members = state.members.clone();
+    let mut member_since = state.member_since.clone();
     let mut added_members = Vec::new();
 
     for address in &addresses {
         if !members.contains(address) {
             members.insert(*address);
+            member_since.insert(*address, ctx.block_production_time as u64);
             added_members.push(*address);
         }
     }
 
     if !added_members.is_empty() {
-        // Generate a process ID
-        let process_id = generate_process_id(&ctx);
+        // Allocate a process ID
+        let mut process_counter = state.process_counter.clone();
+        let process_id = process_counter.next_id(ctx.contract_address);
 
         // Increment event nonce for security
         let event_nonce = state.event_nonce + 1;
 
-        // Create event for the factory
-        let mut event_group = EventGroup::builder();
-        event_group
-            .call(state.factory_address, HANDLE_ORG_EVENT_SHORTNAME)
-            .argument(OrganizationEvent::MembersAdded {
+        // Create event for the factory and the running ballots
+        let event_group = membership_event_group(
+            &state,
+            OrganizationEvent::MembersAdded {
                 members: added_members,
                 organization: ctx.contract_address,
                 timestamp: ctx.block_time as u64,
-                process_id: process_id.clone(),
+                process_id,
                 nonce: event_nonce,
-            })
-            .done();
+            },
+        );
 
         (
             OrganizationState {
                 members,
+                member_since,
+                process_counter,
                 event_nonce,
                 ..state
             },
-            vec![event_group.build()],
+            vec![event_group],
         )
     } else {
         (state, vec![])
@@ -500,48 +959,97 @@ pub fn remove_member(
     state: OrganizationState,
     address: Address,
 ) -> (OrganizationState, Vec<EventGroup>) {
-    assert!(
+    require!(
         state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
         "Only admins can remove a member."
     );
-    assert!(
+    require!(
         state.members.contains(&address),
+        ContractError::NotMember,
         "Could not remove non-member."
     );
-    assert!(state.owner != address, "Cannot remove the owner.");
+    require!(
+        state.owner != address,
+        ContractError::CannotRemoveOwner,
+        "Cannot remove the owner."
+    );
 
     let mut members = state.members.clone();
     members.remove(&address);
+    let mut member_since = state.member_since.clone();
+    member_since.remove(&address);
 
-    // Generate a process ID
-    let process_id = generate_process_id(&ctx);
+    // Allocate a process ID
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
 
     // Increment event nonce
     let event_nonce = state.event_nonce + 1;
 
-    // Create event for the factory
-    let mut event_group = EventGroup::builder();
-    event_group
-        .call(state.factory_address, HANDLE_ORG_EVENT_SHORTNAME)
-        .argument(OrganizationEvent::MembersRemoved {
+    // Create event for the factory and the running ballots
+    let event_group = membership_event_group(
+        &state,
+        OrganizationEvent::MembersRemoved {
             members: vec![address],
             organization: ctx.contract_address,
             timestamp: ctx.block_time as u64,
-            process_id: process_id.clone(),
+            process_id,
             nonce: event_nonce,
-        })
-        .done();
+        },
+    );
 
     (
         OrganizationState {
             members,
+            member_since,
+            process_counter,
             event_nonce,
             ..state
         },
-        vec![event_group.build()],
+        vec![event_group],
     )
 }
 
+/// Ballots that still accept membership changes, those last reported as active or paused
+fn running_ballots(state: &OrganizationState) -> Vec<Address> {
+    state
+        .ballot_statuses
+        .iter()
+        .filter(|(_, status)| matches!(status, BallotStatus::Active {} | BallotStatus::Paused {}))
+        .map(|(ballot, _)| *ballot)
+        .collect()
+}
+
+/// Builds the event group announcing a membership change.
+///
+/// The event goes to the factory and to every running ballot, which applies it according to
+/// its membership policy.
+///
+/// # Arguments
+///
+/// * `state` - the current state of the organization.
+/// * `event` - the `MembersAdded` or `MembersRemoved` event.
+///
+/// # Returns
+///
+/// The event group calling the factory and the running ballots.
+///
+fn membership_event_group(state: &OrganizationState, event: OrganizationEvent) -> EventGroup {
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(state.factory_address, HANDLE_ORG_EVENT_SHORTNAME)
+        .argument(event.clone())
+        .done();
+    for ballot in running_ballots(state) {
+        event_group
+            .call(ballot, BALLOT_HANDLE_ORG_EVENT_SHORTNAME)
+            .argument(event.clone())
+            .done();
+    }
+    event_group.build()
+}
+
 /// Adds a proposal to the organization.
 ///
 /// # Arguments
@@ -559,11 +1067,16 @@ pub fn add_ballot(
     state: OrganizationState,
     address: Address,
 ) -> (OrganizationState, Vec<EventGroup>) {
-    assert!(
+    require!(
         state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
         "Only admins can add a ballot."
     );
-    assert!(!state.ballots.contains(&address), "Already a ballot.");
+    require!(
+        !state.ballots.contains(&address),
+        ContractError::AlreadyBallot,
+        "Already a ballot."
+    );
 
     let mut ballots = state.ballots.clone();
     ballots.insert(address);
@@ -584,6 +1097,16 @@ pub fn add_ballot(
 /// * `description` - the description of the ballot.
 /// * `administrator` - the administrator of the ballot.
 /// * `duration_seconds` - the duration of the ballot.
+/// * `membership_policy` - how the ballot follows membership changes while it runs.
+/// * `attachments` - off-chain proposal documents, registered by their SHA-256 hash.
+/// * `eligibility` - who may vote, organization members or token holders.
+/// * `result_disclosure` - who gets to see the result of the ballot.
+/// * `participation_mode` - whether the addresses taking part in the ballot are public.
+/// * `ballot_mode` - whether the ballot is a vote, a sortition draw or a budget.
+/// * `runoff_rule` - whether a result without majority triggers a runoff.
+/// * `roles` - the talliers, cancellers and observers of the ballot.
+/// * `approvals` - how many administrators must approve cancelling and syncing voters.
+/// * `min_tenure_days` - how many days before the ballot opens members must have joined to vote.
 ///
 /// # Returns
 ///
@@ -598,63 +1121,296 @@ fn deploy_ballot(
     description: String,
     administrator: Address,
     duration_seconds: u64,
+    membership_policy: MembershipPolicy,
+    attachments: Vec<Attachment>,
+    eligibility: Eligibility,
+    result_disclosure: ResultDisclosure,
+    participation_mode: ParticipationMode,
+    ballot_mode: BallotMode,
+    runoff_rule: RunoffRule,
+    roles: BallotRoles,
+    approvals: ApprovalSettings,
+    min_tenure_days: u32,
 ) -> (OrganizationState, Vec<EventGroup>) {
-    let ballot_contract_address = Address {
-        address_type: AddressType::ZkContract,
-        identifier: ctx.original_transaction.bytes[12..32].try_into().unwrap(),
-    };
-    let ballot_title = title.clone();
+    let ballot_contract_address = new_ballot_address(&ctx);
 
-    assert!(
+    require!(
         state.members.len() >= 3,
+        ContractError::TooFewVoters,
         "Organization must have at least 3 members to maintain vote privacy"
     );
-
-    // Generate a process ID for this ballot deployment
-    let process_id = generate_process_id(&ctx);
-
-    // Track this ballot process
-    let mut ballot_processes = state.ballot_processes.clone();
-    ballot_processes.insert(process_id.clone(), BallotProcessState::Created {});
-
-    // Increment event nonce
-    let event_nonce = state.event_nonce + 1;
-
-    let mut event_group = EventGroup::builder();
-
-    assert!(
+    if min_tenure_days > 0 {
+        require!(
+            min_tenure_days <= MAX_TENURE_DAYS,
+            ContractError::InvalidTenure,
+            "Membership tenure cannot exceed {} days.",
+            MAX_TENURE_DAYS
+        );
+        require!(
+            matches!(membership_policy, MembershipPolicy::Snapshot {})
+                && matches!(eligibility, Eligibility::Membership {}),
+            ContractError::InvalidTenure,
+            "Membership tenure requires a membership snapshot."
+        );
+    }
+    require!(
         state.administrators.contains(&administrator),
+        ContractError::NotAdministrator,
         "Administrator must be one of the organization administrators."
     );
+    for attachment in &attachments {
+        require!(
+            attachment.sha256.len() == 64
+                && attachment
+                    .sha256
+                    .chars()
+                    .all(|c| matches!(c, '0'..='9' | 'a'..='f')),
+            ContractError::InvalidAttachment,
+            "Attachment hash must be 64 lowercase hex characters."
+        );
+    }
+    if let ResultDisclosure::Administrators { administrators } = &result_disclosure {
+        for recipient in administrators {
+            require!(
+                state.administrators.contains(recipient),
+                ContractError::NotAdministrator,
+                "Result recipients must be organization administrators."
+            );
+        }
+    }
+    for address in roles.talliers.iter().chain(roles.cancellers.iter()) {
+        require!(
+            state.administrators.contains(address),
+            ContractError::NotAdministrator,
+            "Talliers and cancellers must be organization administrators."
+        );
+    }
+    require!(
+        approvals.threshold as usize <= state.administrators.len(),
+        ContractError::InvalidApprovalPolicy,
+        "Approval threshold cannot exceed the number of administrators."
+    );
 
-    // Convert members set to vec for ballot init
-    // Using a "snapshot" approach - eligible voters are fixed at ballot creation time
-    // This provides clarity about who can participate and prevents mid-vote manipulation
-    let eligible_voters: Vec<Address> = state.members.iter().copied().collect();
+    // Commit to a snapshot of the members for ballot init
+    // Only the Merkle root is sent, so the deploy payload does not grow with the organization
+    // Voters prove their membership against the root when casting a vote, building the proof
+    // off-chain from the members and their join times
+    // Later membership changes are forwarded to the ballot, which applies them according to
+    // its membership policy
+    let eligible_voters = tenured_members(
+        &state.members,
+        &state.member_since,
+        ctx.block_production_time as u64,
+        min_tenure_days,
+    );
+    require!(
+        eligible_voters.len() >= 3,
+        ContractError::TooFewVoters,
+        "At least 3 members must have joined {} days before the ballot to maintain vote privacy",
+        min_tenure_days
+    );
+    let voter_root = voter_merkle_root(&eligible_voters);
+    let voter_count = eligible_voters.len() as u32;
+
+    // Keep the settings the runoff is deployed with, should the ballot need one
+    let mut runoff_templates = state.runoff_templates.clone();
+    if let RunoffRule::TopTwo {
+        duration_seconds: runoff_duration,
+    } = runoff_rule
+    {
+        runoff_templates.insert(
+            ballot_contract_address,
+            RunoffTemplate {
+                title: title.clone(),
+                description: description.clone(),
+                administrator,
+                duration_seconds: runoff_duration,
+                attachments: attachments.clone(),
+                eligibility,
+                participation_mode,
+                roles: roles.clone(),
+                approvals,
+            },
+        );
+    }
 
-    event_group
-        .call(DEPLOY_ZK_CONTRACT_ADDRESS, DEPLOY_ZK_SHORTNAME)
-        .argument(state.ballot_contract_zkwa.clone())
-        .argument(create_ballot_init_data(
-            options,
-            title,
-            description,
-            ctx.contract_address,
-            administrator,
-            eligible_voters,
-            duration_seconds,
-        ))
-        .argument(state.ballot_contract_abi.clone())
-        .argument(20000000i64) // requiredStakes
-        .argument(Vec::<Vec<i32>>::new()) // allowedJurisdictions
-        .argument(ZK_BINDER_ID) // uniqueId
-        .done();
+    let init_data = create_ballot_init_data(
+        options,
+        title.clone(),
+        description,
+        ctx.contract_address,
+        administrator,
+        voter_root,
+        voter_count,
+        duration_seconds,
+        membership_policy,
+        attachments,
+        eligibility,
+        result_disclosure,
+        participation_mode,
+        ballot_mode,
+        runoff_rule,
+        None,
+        roles,
+        approval_policy(&state, approvals),
+    );
 
-    // No event emission here, only in the callback for successful deployment
+    let state = OrganizationState {
+        runoff_templates,
+        ..state
+    };
 
-    event_group
-        .with_callback(SHORTNAME_DEPLOY_BALLOT_CALLBACK)
-        .with_cost(10000)
+    // A token-gated ballot is only deployed once the token answers a balance query
+    if let Eligibility::TokenBalance { token, .. } = eligibility {
+        let mut event_group = EventGroup::builder();
+        event_group
+            .call(token, TOKEN_BALANCE_AT_SHORTNAME)
+            .argument(ctx.contract_address)
+            .argument(ctx.block_production_time as u64)
+            .done();
+        event_group
+            .with_callback(SHORTNAME_TOKEN_PROBE_CALLBACK)
+            .with_cost(10000)
+            .argument(ballot_contract_address)
+            .argument(title)
+            .argument(init_data)
+            .done();
+        return (state, vec![event_group.build()]);
+    }
+
+    start_ballot_deployment(&ctx, state, ballot_contract_address, title, init_data)
+}
+
+/// Callback of the balance query a token-gated ballot is validated with.
+///
+/// The token must implement the checkpoint token interface, `balance_at(owner, timestamp)`
+/// returning an RPC encoded `u128`. Plain MPC-20 tokens have no such action, so their ballots
+/// fail to deploy instead of failing every voter's eligibility check later.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `callback_ctx` - the context of the callback.
+/// * `state` - the current state of the organization.
+/// * `ballot_contract_address` - the address the ballot will be deployed at.
+/// * `ballot_title` - the title of the ballot.
+/// * `init_data` - the init data of the ballot, see `create_ballot_init_data`.
+///
+/// # Returns
+///
+/// The updated organization state and the deployment events.
+///
+#[callback(shortname = 0x21)]
+fn token_probe_callback(
+    ctx: ContractContext,
+    callback_ctx: CallbackContext,
+    state: OrganizationState,
+    ballot_contract_address: Address,
+    ballot_title: String,
+    init_data: Vec<u8>,
+) -> (OrganizationState, Vec<EventGroup>) {
+    let answered = callback_ctx.success
+        && callback_ctx
+            .results
+            .first()
+            .is_some_and(|result| result.return_data.len() == 16);
+    if answered {
+        return start_ballot_deployment(
+            &ctx,
+            state,
+            ballot_contract_address,
+            ballot_title,
+            init_data,
+        );
+    }
+
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
+    ballot_deploy_failed(
+        &ctx,
+        OrganizationState {
+            process_counter,
+            ..state
+        },
+        ballot_contract_address,
+        process_id,
+        "Token does not implement balance_at".to_string(),
+    )
+}
+
+/// Snapshots the current administrators as approvers of a ballot.
+///
+/// The threshold is capped at the number of administrators, which may have shrunk since the
+/// settings were chosen.
+fn approval_policy(state: &OrganizationState, approvals: ApprovalSettings) -> ApprovalPolicy {
+    let approvers: Vec<Address> = state.administrators.iter().copied().collect();
+    ApprovalPolicy {
+        threshold: approvals.threshold.min(approvers.len() as u32),
+        window_seconds: approvals.window_seconds,
+        approvers,
+    }
+}
+
+/// Address the ballot deployed by the current transaction will get
+fn new_ballot_address(ctx: &ContractContext) -> Address {
+    Address {
+        address_type: AddressType::ZkContract,
+        identifier: ctx.original_transaction.bytes[12..32].try_into().unwrap(),
+    }
+}
+
+/// Deploys a ballot contract with the given init data, tracked as a new ballot process.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the organization.
+/// * `ballot_contract_address` - the address the ballot will be deployed at.
+/// * `ballot_title` - the title of the ballot, reported when it is deployed.
+/// * `init_data` - the init data of the ballot, see `create_ballot_init_data`.
+///
+/// # Returns
+///
+/// The updated organization state and the deployment events.
+///
+fn start_ballot_deployment(
+    ctx: &ContractContext,
+    state: OrganizationState,
+    ballot_contract_address: Address,
+    ballot_title: String,
+    init_data: Vec<u8>,
+) -> (OrganizationState, Vec<EventGroup>) {
+    // Allocate a process ID for this ballot deployment
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
+
+    // Track this ballot process
+    let mut ballot_processes = state.ballot_processes.clone();
+    ballot_processes.record(
+        process_id.clone(),
+        BallotProcessState::Created {},
+        ctx.block_production_time as u64,
+    );
+
+    // Increment event nonce
+    let event_nonce = state.event_nonce + 1;
+
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(DEPLOY_ZK_CONTRACT_ADDRESS, DEPLOY_ZK_SHORTNAME)
+        .argument(state.ballot_contract_zkwa.clone())
+        .argument(init_data)
+        .argument(state.ballot_contract_abi.clone())
+        .argument(20000000i64) // requiredStakes
+        .argument(Vec::<Vec<i32>>::new()) // allowedJurisdictions
+        .argument(ZK_BINDER_ID) // uniqueId
+        .done();
+
+    // No event emission here, only in the callback for successful deployment
+
+    event_group
+        .with_callback(SHORTNAME_DEPLOY_BALLOT_CALLBACK)
+        .with_cost(10000)
         .argument(ballot_contract_address)
         .argument(ballot_title)
         .argument(process_id)
@@ -663,6 +1419,7 @@ fn deploy_ballot(
     (
         OrganizationState {
             ballot_processes,
+            process_counter,
             event_nonce,
             ..state
         },
@@ -697,8 +1454,8 @@ fn deploy_ballot_callback(
     let event_nonce = state.event_nonce + 1;
 
     // Check if we already processed this callback (handles potential replays)
-    if let Some(existing_state) = ballot_processes.get(&process_id) {
-        match existing_state {
+    if let Some(existing) = ballot_processes.records.get(&process_id) {
+        match existing.state {
             BallotProcessState::Created {} => {} // Continue processing
             _ => {
                 // This callback has already been processed, return current state
@@ -709,7 +1466,11 @@ fn deploy_ballot_callback(
 
     if callback_ctx.success {
         // Mark process as deployed
-        ballot_processes.insert(process_id.clone(), BallotProcessState::Deployed {});
+        ballot_processes.record(
+            process_id.clone(),
+            BallotProcessState::Deployed {},
+            ctx.block_production_time as u64,
+        );
 
         // Add ballot to tracked ballots
         let mut ballots = state.ballots.clone();
@@ -756,50 +1517,94 @@ fn deploy_ballot_callback(
             event_groups,
         )
     } else {
-        // Mark process as failed/cancelled
-        ballot_processes.insert(process_id.clone(), BallotProcessState::Cancelled {});
-
         // Use a generic error message since detailed error info isn't available in CallbackContext
-        let failure_reason = "Deployment callback failed".to_string();
+        ballot_deploy_failed(
+            &ctx,
+            state,
+            ballot_contract_address,
+            process_id,
+            "Deployment callback failed".to_string(),
+        )
+    }
+}
 
-        // Create event groups
-        let mut event_groups = Vec::new();
+/// Records that a ballot could not be deployed and reports the failure.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the organization.
+/// * `ballot_contract_address` - the address the ballot would have been deployed at.
+/// * `process_id` - the process of the deployment.
+/// * `failure_reason` - why the deployment failed.
+///
+/// # Returns
+///
+/// The updated organization state and the failure events.
+///
+fn ballot_deploy_failed(
+    ctx: &ContractContext,
+    state: OrganizationState,
+    ballot_contract_address: Address,
+    process_id: String,
+    failure_reason: String,
+) -> (OrganizationState, Vec<EventGroup>) {
+    let event_nonce = state.event_nonce + 1;
 
-        // Emit failure event to self for tracking
-        let mut self_event_group = EventGroup::builder();
-        self_event_group
-            .call(ctx.contract_address, BALLOT_DEPLOY_FAILED_SHORTNAME)
-            .argument(OrganizationEvent::BallotDeployFailed {
-                organization: ctx.contract_address,
-                reason: failure_reason.clone(),
-                timestamp: ctx.block_time as u64,
-                process_id: process_id.clone(),
-            })
-            .done();
-        event_groups.push(self_event_group.build());
+    // The ballot does not exist, so it will never need a runoff
+    let mut runoff_templates = state.runoff_templates.clone();
+    runoff_templates.remove(&ballot_contract_address);
+    let mut runoff_parents = state.runoff_parents.clone();
+    runoff_parents.remove(&ballot_contract_address);
 
-        // Also notify the Factory about the failure
-        let mut factory_event_group = EventGroup::builder();
-        factory_event_group
-            .call(state.factory_address, HANDLE_ORG_EVENT_SHORTNAME)
-            .argument(OrganizationEvent::BallotDeployFailed {
-                organization: ctx.contract_address,
-                reason: failure_reason,
-                timestamp: ctx.block_time as u64,
-                process_id,
-            })
-            .done();
-        event_groups.push(factory_event_group.build());
+    // Mark process as failed/cancelled
+    let mut ballot_processes = state.ballot_processes.clone();
+    ballot_processes.record_failure(
+        process_id.clone(),
+        BallotProcessState::Cancelled {},
+        ctx.block_production_time as u64,
+        failure_reason.clone(),
+    );
 
-        (
-            OrganizationState {
-                ballot_processes,
-                event_nonce,
-                ..state
-            },
-            event_groups,
-        )
-    }
+    // Create event groups
+    let mut event_groups = Vec::new();
+
+    // Emit failure event to self for tracking
+    let mut self_event_group = EventGroup::builder();
+    self_event_group
+        .call(ctx.contract_address, BALLOT_DEPLOY_FAILED_SHORTNAME)
+        .argument(OrganizationEvent::BallotDeployFailed {
+            organization: ctx.contract_address,
+            reason: failure_reason.clone(),
+            timestamp: ctx.block_time as u64,
+            process_id: process_id.clone(),
+        })
+        .done();
+    event_groups.push(self_event_group.build());
+
+    // Also notify the Factory about the failure
+    let mut factory_event_group = EventGroup::builder();
+    factory_event_group
+        .call(state.factory_address, HANDLE_ORG_EVENT_SHORTNAME)
+        .argument(OrganizationEvent::BallotDeployFailed {
+            organization: ctx.contract_address,
+            reason: failure_reason,
+            timestamp: ctx.block_time as u64,
+            process_id,
+        })
+        .done();
+    event_groups.push(factory_event_group.build());
+
+    (
+        OrganizationState {
+            ballot_processes,
+            event_nonce,
+            runoff_templates,
+            runoff_parents,
+            ..state
+        },
+        event_groups,
+    )
 }
 
 /// Create the initial data for a ballot.
@@ -810,6 +1615,20 @@ fn deploy_ballot_callback(
 /// * `title` - the title of the ballot.
 /// * `description` - the description of the ballot.
 /// * `organization` - the organization of the ballot.
+/// * `administrator` - the administrator of the ballot.
+/// * `voter_root` - the Merkle root of the eligible voter snapshot.
+/// * `voter_count` - the number of voters in the snapshot.
+/// * `duration_seconds` - the duration of the ballot.
+/// * `membership_policy` - how the ballot follows membership changes.
+/// * `attachments` - off-chain proposal documents.
+/// * `eligibility` - who may vote on the ballot.
+/// * `result_disclosure` - who gets to see the result of the ballot.
+/// * `participation_mode` - whether the addresses taking part in the ballot are public.
+/// * `ballot_mode` - whether the ballot is a vote, a sortition draw or a budget.
+/// * `runoff_rule` - whether a result without majority triggers a runoff.
+/// * `parent_ballot` - the ballot the new ballot is a runoff of.
+/// * `roles` - the talliers, cancellers and observers of the ballot.
+/// * `approval_policy` - who approves cancelling and syncing voters.
 ///
 /// # Returns
 ///
@@ -820,8 +1639,19 @@ fn create_ballot_init_data(
     description: String,
     organization: Address,
     administrator: Address,
-    eligible_voters: Vec<Address>,
+    voter_root: Hash,
+    voter_count: u32,
     duration_seconds: u64,
+    membership_policy: MembershipPolicy,
+    attachments: Vec<Attachment>,
+    eligibility: Eligibility,
+    result_disclosure: ResultDisclosure,
+    participation_mode: ParticipationMode,
+    ballot_mode: BallotMode,
+    runoff_rule: RunoffRule,
+    parent_ballot: Option<Address>,
+    roles: BallotRoles,
+    approval_policy: ApprovalPolicy,
 ) -> Vec<u8> {
     let mut bytes: Vec<u8> = vec![0xff, 0xff, 0xff, 0xff, 0x0f];
     WriteRPC::rpc_write_to(&options, &mut bytes).unwrap();
@@ -829,8 +1659,19 @@ fn create_ballot_init_data(
     WriteRPC::rpc_write_to(&description, &mut bytes).unwrap();
     WriteRPC::rpc_write_to(&organization, &mut bytes).unwrap();
     WriteRPC::rpc_write_to(&administrator, &mut bytes).unwrap();
-    WriteRPC::rpc_write_to(&eligible_voters, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&voter_root, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&voter_count, &mut bytes).unwrap();
     WriteRPC::rpc_write_to(&duration_seconds, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&membership_policy, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&attachments, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&eligibility, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&result_disclosure, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&participation_mode, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&ballot_mode, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&runoff_rule, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&parent_ballot, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&roles, &mut bytes).unwrap();
+    WriteRPC::rpc_write_to(&approval_policy, &mut bytes).unwrap();
     bytes
 }
 
@@ -850,17 +1691,423 @@ fn handle_ballot_deployed_event(
 
             // Update the ballot process state
             let mut ballot_processes = state.ballot_processes.clone();
-            ballot_processes.insert(process_id, BallotProcessState::Active {});
+            ballot_processes.record(
+                process_id,
+                BallotProcessState::Active {},
+                ctx.block_production_time as u64,
+            );
+
+            let mut ballot_statuses = state.ballot_statuses.clone();
+            ballot_statuses.insert(ballot, BallotStatus::Active {});
 
             (
                 OrganizationState {
                     ballots,
                     ballot_processes,
+                    ballot_statuses,
+                    ..state
+                },
+                vec![],
+            )
+        }
+        _ => fail(ContractError::UnexpectedEvent, "Unexpected event type"),
+    }
+}
+
+/// Handles events reported by one of the organization's ballots.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the organization.
+/// * `event` - the event reported by the ballot.
+///
+/// # Returns
+///
+/// The updated organization state reflecting the reported ballot status, and the deployment
+/// of a runoff if the ballot requires one.
+///
+#[action(shortname = 0x41)]
+fn handle_ballot_event(
+    ctx: ContractContext,
+    state: OrganizationState,
+    event: BallotEvent,
+) -> (OrganizationState, Vec<EventGroup>) {
+    require!(
+        state.ballots.contains(&ctx.sender),
+        ContractError::OnlyOrganizationBallot,
+        "Only ballots of this organization can report events."
+    );
+
+    match event {
+        BallotEvent::StatusChanged { status, .. } => {
+            let mut ballot_statuses = state.ballot_statuses.clone();
+            ballot_statuses.insert(ctx.sender, status);
+
+            (
+                OrganizationState {
+                    ballot_statuses,
                     ..state
                 },
                 vec![],
             )
         }
-        _ => panic!("Unexpected event type"),
+        BallotEvent::RunoffRequired {
+            options,
+            voter_root,
+            voter_count,
+            ..
+        } => deploy_runoff(ctx, state, options, voter_root, voter_count),
+        _ => (state, vec![]),
+    }
+}
+
+/// Deploys the runoff of the ballot that sent the event, with the settings recorded when the
+/// ballot was deployed and the voter snapshot of the ballot.
+///
+/// Each ballot gets at most one runoff. If the administrator of the ballot is no longer an
+/// administrator of the organization, the owner administers the runoff. Talliers and
+/// cancellers that are no longer administrators lose their role.
+fn deploy_runoff(
+    ctx: ContractContext,
+    state: OrganizationState,
+    options: Vec<String>,
+    voter_root: Hash,
+    voter_count: u32,
+) -> (OrganizationState, Vec<EventGroup>) {
+    let parent_ballot = ctx.sender;
+    let Some(template) = state.runoff_templates.get(&parent_ballot).cloned() else {
+        return (state, vec![]);
+    };
+    let ballot_contract_address = new_ballot_address(&ctx);
+    let administrator = if state.administrators.contains(&template.administrator) {
+        template.administrator
+    } else {
+        state.owner
+    };
+    let title = format!("Runoff: {}", template.title);
+    let talliers: Vec<Address> = template
+        .roles
+        .talliers
+        .iter()
+        .filter(|address| state.administrators.contains(address))
+        .copied()
+        .collect();
+    let mut cancellers: Vec<Address> = template
+        .roles
+        .cancellers
+        .iter()
+        .filter(|address| state.administrators.contains(address))
+        .copied()
+        .collect();
+    // A cancellation needs a confirmation by someone besides the administrator
+    if cancellers
+        .iter()
+        .all(|canceller| *canceller == administrator)
+    {
+        cancellers.clear();
+    }
+    let roles = BallotRoles {
+        talliers,
+        cancellers,
+        observers: template.roles.observers,
+    };
+
+    let mut runoff_templates = state.runoff_templates.clone();
+    runoff_templates.remove(&parent_ballot);
+    let mut runoff_parents = state.runoff_parents.clone();
+    runoff_parents.insert(ballot_contract_address, parent_ballot);
+
+    let init_data = create_ballot_init_data(
+        options,
+        title.clone(),
+        template.description,
+        ctx.contract_address,
+        administrator,
+        voter_root,
+        voter_count,
+        template.duration_seconds,
+        MembershipPolicy::Snapshot {},
+        template.attachments,
+        template.eligibility,
+        ResultDisclosure::Public {},
+        template.participation_mode,
+        BallotMode::Vote {},
+        RunoffRule::None {},
+        Some(parent_ballot),
+        roles,
+        approval_policy(&state, template.approvals),
+    );
+
+    start_ballot_deployment(
+        &ctx,
+        OrganizationState {
+            runoff_templates,
+            runoff_parents,
+            ..state
+        },
+        ballot_contract_address,
+        title,
+        init_data,
+    )
+}
+
+/// Replaces the voter snapshot of a running ballot with the current members.
+///
+/// Only ballots deployed with `MembershipPolicy::LiveSync` accept the new snapshot. Ballots
+/// whose approval policy requires approvals only propose it, to be confirmed by their approvers.
+/// Only the root and size are sent, voters rebuild the snapshot from the members off-chain.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the organization.
+/// * `ballot` - the ballot to sync.
+///
+/// # Returns
+///
+/// The unchanged organization state and the call syncing the ballot.
+///
+#[action(shortname = 0x0B)]
+pub fn sync_ballot_voters(
+    ctx: ContractContext,
+    state: OrganizationState,
+    ballot: Address,
+) -> (OrganizationState, Vec<EventGroup>) {
+    require!(
+        state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
+        "Only admins can sync the voters of a ballot."
+    );
+    require!(
+        state.ballots.contains(&ballot),
+        ContractError::NotBallot,
+        "Not a ballot of this organization."
+    );
+    let voters: Vec<Address> = state.members.iter().copied().collect();
+    require!(
+        voters.len() >= 3,
+        ContractError::TooFewVoters,
+        "Organization must have at least 3 members to maintain vote privacy"
+    );
+
+    let mut event_group = EventGroup::builder();
+    event_group
+        .call(ballot, BALLOT_SYNC_VOTERS_SHORTNAME)
+        .argument(voter_merkle_root(&voters))
+        .argument(voters.len() as u32)
+        .done();
+
+    (state, vec![event_group.build()])
+}
+
+/// Prunes finished ballot process records older than the given age.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the organization.
+/// * `max_age_seconds` - the age above which finished records are pruned.
+///
+/// # Returns
+///
+/// The updated organization state, with pruned records only kept as counts.
+///
+#[action(shortname = 0x09)]
+pub fn prune_processes(
+    ctx: ContractContext,
+    state: OrganizationState,
+    max_age_seconds: u64,
+) -> OrganizationState {
+    require!(
+        state.administrators.contains(&ctx.sender),
+        ContractError::OnlyAdministrator,
+        "Only administrators can prune processes."
+    );
+
+    let cutoff = (ctx.block_production_time as u64).saturating_sub(max_age_seconds * 1000);
+    let mut ballot_processes = state.ballot_processes.clone();
+    ballot_processes.prune_older_than(cutoff);
+
+    OrganizationState {
+        ballot_processes,
+        ..state
+    }
+}
+
+/// Sets who may upgrade the organization contract.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the organization.
+/// * `upgrader` - the address allowed to upgrade the contract, `None` disables upgrades.
+///
+/// # Returns
+///
+/// The updated organization state.
+///
+#[action(shortname = 0x0A)]
+pub fn set_upgrader(
+    ctx: ContractContext,
+    state: OrganizationState,
+    upgrader: Option<Address>,
+) -> OrganizationState {
+    require!(
+        state.owner == ctx.sender,
+        ContractError::OnlyOwner,
+        "Only the owner can set the upgrader."
+    );
+
+    OrganizationState { upgrader, ..state }
+}
+
+/// Decides whether the contract may be upgraded.
+///
+/// Only the upgrader set by the owner can upgrade the organization.
+#[upgrade_is_allowed]
+fn is_upgrade_allowed(
+    ctx: ContractContext,
+    state: OrganizationState,
+    _old_contract_hashes: ContractHashes,
+    _new_contract_hashes: ContractHashes,
+    _new_contract_rpc: Vec<u8>,
+) -> bool {
+    state.upgrader == Some(ctx.sender)
+}
+
+/// Migrates the state of the previous contract version to the current layout.
+///
+/// Version 1 is the first versioned layout, so there is nothing to migrate yet and a state of
+/// version 1 is read as `OrganizationState` unchanged. Organizations deployed before states
+/// were versioned have a different layout and cannot be upgraded. When the layout changes, bump
+/// `STATE_VERSION`, keep the replaced struct as `OrganizationStateV<n>` and convert it field by
+/// field here.
+#[upgrade]
+fn upgrade(ctx: ContractContext, state: OrganizationState) -> OrganizationState {
+    require!(
+        state.state_version <= STATE_VERSION,
+        ContractError::UnsupportedStateVersion,
+        "Cannot migrate state version {} to {}",
+        state.state_version,
+        STATE_VERSION
+    );
+
+    OrganizationState {
+        state_version: STATE_VERSION,
+        ..state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(id: u8) -> Address {
+        Address {
+            address_type: AddressType::Account,
+            identifier: [id; 20],
+        }
+    }
+
+    #[test]
+    fn voter_root_of_single_voter_is_its_leaf() {
+        let voter = account(1);
+        assert_eq!(
+            voter_merkle_root(&[voter]).bytes,
+            voter_leaf(0, &voter).bytes
+        );
+    }
+
+    #[test]
+    fn voter_root_carries_unpaired_node_up() {
+        let (a, b, c) = (account(1), account(2), account(3));
+        let ab = voter_node(&voter_leaf(0, &a), &voter_leaf(1, &b));
+        assert_eq!(
+            voter_merkle_root(&[a, b, c]).bytes,
+            voter_node(&ab, &voter_leaf(2, &c)).bytes
+        );
+    }
+
+    #[test]
+    fn voter_root_depends_on_voters() {
+        let (a, b, c) = (account(1), account(2), account(3));
+        assert_ne!(
+            voter_merkle_root(&[a, b]).bytes,
+            voter_merkle_root(&[a, c]).bytes
+        );
+    }
+
+    #[test]
+    fn voter_root_depends_on_voter_positions() {
+        let (a, b) = (account(1), account(2));
+        assert_ne!(
+            voter_merkle_root(&[a, b]).bytes,
+            voter_merkle_root(&[b, a]).bytes
+        );
+    }
+
+    const DAY: u64 = 86_400_000;
+
+    fn members(ids: &[u8]) -> SortedVecSet<Address> {
+        let mut members = SortedVecSet::new();
+        for id in ids {
+            members.insert(account(*id));
+        }
+        members
+    }
+
+    #[test]
+    fn tenure_of_zero_days_keeps_every_member() {
+        let mut member_since = SortedVecMap::new();
+        member_since.insert(account(1), 10 * DAY);
+        member_since.insert(account(2), 10 * DAY);
+
+        assert_eq!(
+            tenured_members(&members(&[1, 2]), &member_since, 10 * DAY, 0),
+            vec![account(1), account(2)]
+        );
+    }
+
+    #[test]
+    fn tenure_excludes_members_that_joined_later() {
+        let mut member_since = SortedVecMap::new();
+        member_since.insert(account(1), 0);
+        member_since.insert(account(2), 3 * DAY);
+        member_since.insert(account(3), 3 * DAY + 1);
+
+        assert_eq!(
+            tenured_members(&members(&[1, 2, 3]), &member_since, 10 * DAY, 7),
+            vec![account(1), account(2)]
+        );
+    }
+
+    #[test]
+    fn members_without_join_time_never_qualify() {
+        let mut member_since = SortedVecMap::new();
+        member_since.insert(account(2), 0);
+
+        assert_eq!(
+            tenured_members(&members(&[1, 2]), &member_since, 10 * DAY, 0),
+            vec![account(2)]
+        );
+    }
+
+    #[test]
+    fn tenure_longer_than_chain_history_keeps_only_old_members() {
+        let mut member_since = SortedVecMap::new();
+        member_since.insert(account(1), 0);
+        member_since.insert(account(2), 1);
+
+        assert_eq!(
+            tenured_members(&members(&[1, 2]), &member_since, DAY, 365),
+            vec![account(1)]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot build a voter snapshot without voters.")]
+    fn voter_root_needs_voters() {
+        voter_merkle_root(&[]);
     }
 }
@@ -0,0 +1,182 @@
+//! Error codes shared by the Sekiva contracts.
+
+/// Errors the Sekiva contracts fail with.
+///
+/// Codes are stable and grouped by hundreds: 1xx authorization, 2xx invalid input, 3xx
+/// membership, 4xx ballot state, 5xx voting, 6xx tallying, 7xx events and 8xx upgrades. New
+/// variants get new codes, existing codes are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractError {
+    /// The sender is not the owner of the organization
+    OnlyOwner = 101,
+    /// The sender is not an administrator of the organization
+    OnlyAdministrator = 102,
+    /// The sender is not the administrator of the ballot
+    OnlyBallotAdministrator = 103,
+    /// The sender is not the admin of the factory
+    OnlyFactoryAdmin = 104,
+    /// The sender is not the organization the ballot belongs to
+    OnlyParentOrganization = 105,
+    /// The sender is not a ballot of the organization
+    OnlyOrganizationBallot = 106,
+    /// The sender is neither the administrator nor the organization of the ballot
+    OnlyBallotAdministratorOrOrganization = 107,
+    /// The sender may not run the tally of the ballot
+    OnlyTallier = 108,
+    /// The sender may not cancel the ballot
+    OnlyCanceller = 109,
+    /// The sender may not approve actions on the ballot
+    OnlyApprover = 110,
+
+    /// A required value is empty
+    EmptyValue = 201,
+    /// The number of ballot options is out of range
+    InvalidOptionCount = 202,
+    /// The ballot duration is out of range
+    InvalidDuration = 203,
+    /// An attachment is malformed
+    InvalidAttachment = 204,
+    /// More attachments than supported were given
+    TooManyAttachments = 205,
+    /// An address cannot be used in the given role
+    InvalidAddress = 206,
+    /// The number of seats to draw is out of range
+    InvalidSeatCount = 207,
+    /// The runoff rule does not fit the ballot
+    InvalidRunoffRule = 208,
+    /// The budget or the project costs are out of range
+    InvalidBudget = 209,
+    /// The approval threshold or window is out of range
+    InvalidApprovalPolicy = 210,
+    /// The membership tenure requirement is out of range or unsupported
+    InvalidTenure = 211,
+    /// The membership proof does not hold for the address and position
+    InvalidMembershipProof = 212,
+    /// Anonymous voting needs an approver other than the administrator to issue token shares
+    NoTokenCoIssuer = 213,
+
+    /// The address is already a member
+    AlreadyMember = 301,
+    /// The address is not a member
+    NotMember = 302,
+    /// The address is already an administrator
+    AlreadyAdministrator = 303,
+    /// The address is not an administrator
+    NotAdministrator = 304,
+    /// The owner cannot be removed
+    CannotRemoveOwner = 305,
+    /// The ballot is already registered
+    AlreadyBallot = 306,
+    /// Too few members or voters to keep votes private
+    TooFewVoters = 307,
+    /// No member contributed randomness to the draw
+    NoDrawContributions = 308,
+    /// The sender already approved the action
+    AlreadyApproved = 309,
+    /// The address is not a ballot of the organization
+    NotBallot = 310,
+
+    /// The ballot is not active
+    BallotNotActive = 401,
+    /// The ballot is not tallying
+    BallotNotTallying = 402,
+    /// The ballot is not paused
+    BallotNotPaused = 403,
+    /// The ballot has already ended
+    BallotEnded = 404,
+    /// The action is not allowed in the current ballot state
+    InvalidBallotState = 405,
+    /// The ballot metadata can no longer be changed this way
+    MetadataLocked = 406,
+    /// The membership policy of the ballot does not allow the action
+    MembershipPolicyViolation = 407,
+    /// The ballot is not token gated
+    NotTokenGated = 408,
+    /// The action does not match the participation mode of the ballot
+    WrongParticipationMode = 409,
+    /// The action does not match the mode of the ballot, voting or sortition
+    WrongBallotMode = 410,
+    /// No cancellation of the ballot is waiting for confirmation
+    NoCancellationProposed = 411,
+    /// The action must be approved by several administrators
+    ApprovalRequired = 412,
+    /// The member drawn for the seat is already published
+    SeatAlreadyPublished = 413,
+
+    /// The voting period has ended
+    VotingEnded = 501,
+    /// The sender is not eligible to vote
+    NotEligible = 502,
+    /// The sender has already voted
+    AlreadyVoted = 503,
+    /// The token balance of the sender is already known
+    TokenBalanceKnown = 504,
+    /// The token balance query failed
+    TokenQueryFailed = 505,
+    /// The sender has already issued a token share to the voter
+    TokenShareIssued = 506,
+    /// The sender has already contributed randomness to the draw
+    AlreadyContributed = 507,
+    /// Anonymous voting token shares can no longer be issued
+    IssuanceClosed = 508,
+    /// Anonymous voting opens once token issuance has closed
+    IssuanceOpen = 509,
+    /// The anonymous votes awaiting their check fill every issued voting token
+    VoteLimitReached = 510,
+
+    /// A computation is in flight
+    ComputationInProgress = 601,
+    /// Too few votes were cast to tally
+    TooFewVotes = 602,
+    /// The tally is still in progress
+    TallyInProgress = 603,
+    /// The tally has been attempted too many times
+    TallyAttemptsExhausted = 604,
+    /// The computation produced unexpected output
+    UnexpectedOutput = 605,
+    /// The ballot has no private result
+    NoPrivateResult = 606,
+    /// The sender may not receive the private result
+    NotResultRecipient = 607,
+    /// The private result was already disclosed to the sender
+    ResultAlreadyDisclosed = 608,
+    /// No range check of votes is running
+    NoVoteCheck = 609,
+    /// The secret variable or attestation does not exist
+    MissingVariable = 610,
+
+    /// The event cannot be handled by the receiver
+    UnexpectedEvent = 701,
+
+    /// The state version cannot be migrated to the layout of this contract
+    UnsupportedStateVersion = 801,
+}
+
+impl ContractError {
+    /// The stable numeric code of the error
+    pub fn code(self) -> u32 {
+        self as u32
+    }
+
+    /// Formats the panic message of the error as `[SEKIVA-<code>] <detail>`
+    pub fn panic_message(self, detail: &str) -> String {
+        format!("[SEKIVA-{}] {}", self.code(), detail)
+    }
+}
+
+/// Fails the transaction with the given error and detail message
+pub fn fail(error: ContractError, detail: &str) -> ! {
+    panic!("{}", error.panic_message(detail))
+}
+
+/// Fails the transaction with the given error unless the condition holds.
+///
+/// The detail message takes the same arguments as `format!`.
+#[macro_export]
+macro_rules! require {
+    ($condition:expr, $error:expr, $($detail:tt)+) => {
+        if !$condition {
+            $crate::fail($error, &format!($($detail)+))
+        }
+    };
+}
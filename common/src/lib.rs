@@ -0,0 +1,47 @@
+#![doc = include_str!("../README.md")]
+
+mod errors;
+
+pub use errors::{fail, ContractError};
+
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::address::Address;
+use pbc_traits::WriteRPC;
+use read_write_state_derive::ReadWriteState;
+
+/// Allocates process IDs for a single contract.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+pub struct ProcessCounter {
+    next: u64, // Sequence number of the next process
+}
+
+impl ProcessCounter {
+    /// Creates a counter starting at sequence number 0
+    pub fn new() -> Self {
+        ProcessCounter { next: 0 }
+    }
+
+    /// Allocates the next process ID of the given contract
+    pub fn next_id(&mut self, contract: Address) -> String {
+        let process_id = process_id(contract, self.next);
+        self.next += 1;
+        process_id
+    }
+}
+
+impl Default for ProcessCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats the process ID with the given sequence number for the contract
+pub fn process_id(contract: Address, sequence: u64) -> String {
+    let mut bytes: Vec<u8> = vec![];
+    contract.rpc_write_to(&mut bytes).unwrap();
+    let address_hex = bytes
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    format!("{}-{}", address_hex, sequence)
+}
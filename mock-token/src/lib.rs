@@ -0,0 +1,137 @@
+#![doc = include_str!("../README.md")]
+#![allow(unused_variables)]
+
+#[macro_use]
+extern crate pbc_contract_codegen;
+extern crate pbc_contract_common;
+
+use create_type_spec_derive::CreateTypeSpec;
+use pbc_contract_common::address::Address;
+use pbc_contract_common::context::ContractContext;
+use pbc_contract_common::events::EventGroup;
+use pbc_contract_common::sorted_vec_map::SortedVecMap;
+use read_write_state_derive::ReadWriteState;
+
+/// Balance of an account from `timestamp` until the next checkpoint
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct Checkpoint {
+    timestamp: u64,
+    balance: u128,
+}
+
+/// The state of the token, which is persisted on-chain.
+#[state]
+pub struct TokenState {
+    total_supply: u128,
+    checkpoints: SortedVecMap<Address, Vec<Checkpoint>>, // Balance history per account, oldest first
+}
+
+/// Returns the balance of the owner at the given time
+fn balance_at_time(state: &TokenState, owner: &Address, timestamp: u64) -> u128 {
+    state
+        .checkpoints
+        .get(owner)
+        .and_then(|checkpoints| {
+            checkpoints
+                .iter()
+                .rev()
+                .find(|checkpoint| checkpoint.timestamp <= timestamp)
+        })
+        .map_or(0, |checkpoint| checkpoint.balance)
+}
+
+/// Records a new balance for the owner
+fn set_balance(state: &mut TokenState, owner: Address, timestamp: u64, balance: u128) {
+    let mut checkpoints = state.checkpoints.get(&owner).cloned().unwrap_or_default();
+    match checkpoints.last_mut() {
+        Some(last) if last.timestamp == timestamp => last.balance = balance,
+        _ => checkpoints.push(Checkpoint { timestamp, balance }),
+    }
+    state.checkpoints.insert(owner, checkpoints);
+}
+
+/// Initialize the token, minting the total supply to the deployer.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `total_supply` - the number of tokens in existence.
+///
+/// # Returns
+///
+/// The initial state of the token.
+///
+#[init]
+pub fn initialize(ctx: ContractContext, total_supply: u128) -> TokenState {
+    let mut state = TokenState {
+        total_supply,
+        checkpoints: SortedVecMap::new(),
+    };
+    set_balance(
+        &mut state,
+        ctx.sender,
+        ctx.block_production_time as u64,
+        total_supply,
+    );
+    state
+}
+
+/// Transfers tokens from the sender to another account.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the token.
+/// * `to` - the receiver of the tokens.
+/// * `amount` - the number of tokens to transfer.
+///
+/// # Returns
+///
+/// The updated token state reflecting the transfer.
+///
+#[action(shortname = 0x01)]
+pub fn transfer(
+    ctx: ContractContext,
+    mut state: TokenState,
+    to: Address,
+    amount: u128,
+) -> TokenState {
+    let now = ctx.block_production_time as u64;
+    let sender_balance = balance_at_time(&state, &ctx.sender, now);
+    assert!(sender_balance >= amount, "Insufficient balance.");
+
+    set_balance(&mut state, ctx.sender, now, sender_balance - amount);
+    let receiver_balance = balance_at_time(&state, &to, now);
+    set_balance(&mut state, to, now, receiver_balance + amount);
+    state
+}
+
+/// Returns the balance an account held at a point in time.
+///
+/// The balance is returned as an RPC encoded `u128` in the return data of the call.
+///
+/// # Arguments
+///
+/// * `ctx` - the contract context containing information about the sender and the blockchain.
+/// * `state` - the current state of the token.
+/// * `owner` - the account to query.
+/// * `timestamp` - the block production time, in milliseconds, to query the balance at.
+///
+/// # Returns
+///
+/// The unchanged token state and the balance as return data.
+///
+#[action(shortname = 0x20)]
+pub fn balance_at(
+    ctx: ContractContext,
+    state: TokenState,
+    owner: Address,
+    timestamp: u64,
+) -> (TokenState, Vec<EventGroup>) {
+    let balance = balance_at_time(&state, &owner, timestamp);
+
+    let mut event_group = EventGroup::builder();
+    event_group.return_data(balance);
+
+    (state, vec![event_group.build()])
+}
@@ -13,6 +13,7 @@ import BallotCard from "./BallotCard";
 import { BallotStatusD } from "@/contracts/BallotGenerated";
 import { transformBallotStateToCardProps } from "@/lib/ballotUtils";
 import { TransactionDialog } from "@/components/shared/TransactionDialog";
+import { ballotVoterSnapshot, voterMerkleProof } from "@/lib/voterProof";
 
 type ErrorWithMessage = { message: string };
 function isErrorWithMessage(e: unknown): e is ErrorWithMessage {
@@ -121,10 +122,20 @@ const BallotPage = () => {
 
   const handleCastVote = () => {
     console.log("Casting vote with option:", selectedOption);
+    const voters =
+      organization && ballotVoterSnapshot(organization, ballot.state);
+    if (!account || !voters) {
+      console.error("No voter snapshot for ballot", ballotId);
+      return;
+    }
     castVote(
       {
         ballotAddress: ballotId!,
         choice: selectedOption || 0,
+        ...voterMerkleProof(
+          voters,
+          BlockchainAddress.fromString(account.getAddress())
+        ),
       },
       {
         onSuccess: (data) => {
@@ -36,6 +36,7 @@ export class BallotGenerated {
     this._client = client;
   }
   public deserializeBallotState(_input: AbiInput): BallotState {
+    const stateVersion: number = _input.readU32();
     const organization: BlockchainAddress = _input.readAddress();
     const administrator: BlockchainAddress = _input.readAddress();
     const title: string = _input.readString();
@@ -60,28 +61,202 @@ export class BallotGenerated {
       const tally_option: Tally = this.deserializeTally(_input);
       tally = tally_option;
     }
-    const eligibleVoters_vecLength = _input.readI32();
-    const eligibleVoters: BlockchainAddress[] = [];
-    for (let eligibleVoters_i = 0; eligibleVoters_i < eligibleVoters_vecLength; eligibleVoters_i++) {
-      const eligibleVoters_elem: BlockchainAddress = _input.readAddress();
-      eligibleVoters.push(eligibleVoters_elem);
+    const voterRoot: Hash = _input.readHash();
+    const voterCount: number = _input.readU32();
+    const addedVoters_setLength = _input.readI32();
+    const addedVoters: BlockchainAddress[] = [];
+    for (let addedVoters_i = 0; addedVoters_i < addedVoters_setLength; addedVoters_i++) {
+      const addedVoters_elem: BlockchainAddress = _input.readAddress();
+      addedVoters.push(addedVoters_elem);
     }
-    const alreadyVoted_vecLength = _input.readI32();
+    const removedVoters_setLength = _input.readI32();
+    const removedVoters: BlockchainAddress[] = [];
+    for (let removedVoters_i = 0; removedVoters_i < removedVoters_setLength; removedVoters_i++) {
+      const removedVoters_elem: BlockchainAddress = _input.readAddress();
+      removedVoters.push(removedVoters_elem);
+    }
+    const membershipPolicy: MembershipPolicy = this.deserializeMembershipPolicy(_input);
+    const alreadyVoted_setLength = _input.readI32();
     const alreadyVoted: BlockchainAddress[] = [];
-    for (let alreadyVoted_i = 0; alreadyVoted_i < alreadyVoted_vecLength; alreadyVoted_i++) {
+    for (let alreadyVoted_i = 0; alreadyVoted_i < alreadyVoted_setLength; alreadyVoted_i++) {
       const alreadyVoted_elem: BlockchainAddress = _input.readAddress();
       alreadyVoted.push(alreadyVoted_elem);
     }
     const processState: BallotProcessState = this.deserializeBallotProcessState(_input);
     const processId: string = _input.readString();
-    const eventProcesses_mapLength = _input.readI32();
-    const eventProcesses: Map<string, ProcessState> = new Map();
-    for (let eventProcesses_i = 0; eventProcesses_i < eventProcesses_mapLength; eventProcesses_i++) {
-      const eventProcesses_key: string = _input.readString();
-      const eventProcesses_value: ProcessState = this.deserializeProcessState(_input);
-      eventProcesses.set(eventProcesses_key, eventProcesses_value);
+    const eventProcesses: ProcessLog = this.deserializeProcessLog(_input);
+    const processCounter: ProcessCounter = this.deserializeProcessCounter(_input);
+    let tallyStartedAt: Option<BN> = undefined;
+    const tallyStartedAt_isSome = _input.readBoolean();
+    if (tallyStartedAt_isSome) {
+      const tallyStartedAt_option: BN = _input.readU64();
+      tallyStartedAt = tallyStartedAt_option;
+    }
+    const tallyAttempts: number = _input.readU32();
+    let failureReason: Option<string> = undefined;
+    const failureReason_isSome = _input.readBoolean();
+    if (failureReason_isSome) {
+      const failureReason_option: string = _input.readString();
+      failureReason = failureReason_option;
+    }
+    const voteReceipts_mapLength = _input.readI32();
+    const voteReceipts: Map<BlockchainAddress, VoteReceipt> = new Map();
+    for (let voteReceipts_i = 0; voteReceipts_i < voteReceipts_mapLength; voteReceipts_i++) {
+      const voteReceipts_key: BlockchainAddress = _input.readAddress();
+      const voteReceipts_value: VoteReceipt = this.deserializeVoteReceipt(_input);
+      voteReceipts.set(voteReceipts_key, voteReceipts_value);
+    }
+    const talliedVariables_vecLength = _input.readI32();
+    const talliedVariables: number[] = [];
+    for (let talliedVariables_i = 0; talliedVariables_i < talliedVariables_vecLength; talliedVariables_i++) {
+      const talliedVariables_elem: number = _input.readU32();
+      talliedVariables.push(talliedVariables_elem);
+    }
+    let eventListener: Option<BlockchainAddress> = undefined;
+    const eventListener_isSome = _input.readBoolean();
+    if (eventListener_isSome) {
+      const eventListener_option: BlockchainAddress = _input.readAddress();
+      eventListener = eventListener_option;
+    }
+    let pausedAt: Option<BN> = undefined;
+    const pausedAt_isSome = _input.readBoolean();
+    if (pausedAt_isSome) {
+      const pausedAt_option: BN = _input.readU64();
+      pausedAt = pausedAt_option;
+    }
+    const metadataRevisions_vecLength = _input.readI32();
+    const metadataRevisions: MetadataRevision[] = [];
+    for (let metadataRevisions_i = 0; metadataRevisions_i < metadataRevisions_vecLength; metadataRevisions_i++) {
+      const metadataRevisions_elem: MetadataRevision = this.deserializeMetadataRevision(_input);
+      metadataRevisions.push(metadataRevisions_elem);
+    }
+    const attachments_vecLength = _input.readI32();
+    const attachments: Attachment[] = [];
+    for (let attachments_i = 0; attachments_i < attachments_vecLength; attachments_i++) {
+      const attachments_elem: Attachment = this.deserializeAttachment(_input);
+      attachments.push(attachments_elem);
+    }
+    const eligibility: Eligibility = this.deserializeEligibility(_input);
+    const tokenBalances_mapLength = _input.readI32();
+    const tokenBalances: Map<BlockchainAddress, BN> = new Map();
+    for (let tokenBalances_i = 0; tokenBalances_i < tokenBalances_mapLength; tokenBalances_i++) {
+      const tokenBalances_key: BlockchainAddress = _input.readAddress();
+      const tokenBalances_value: BN = _input.readUnsignedBigInteger(16);
+      tokenBalances.set(tokenBalances_key, tokenBalances_value);
+    }
+    const resultDisclosure: ResultDisclosure = this.deserializeResultDisclosure(_input);
+    let privateResult: Option<PrivateResult> = undefined;
+    const privateResult_isSome = _input.readBoolean();
+    if (privateResult_isSome) {
+      const privateResult_option: PrivateResult = this.deserializePrivateResult(_input);
+      privateResult = privateResult_option;
+    }
+    const resultRecipients_setLength = _input.readI32();
+    const resultRecipients: BlockchainAddress[] = [];
+    for (let resultRecipients_i = 0; resultRecipients_i < resultRecipients_setLength; resultRecipients_i++) {
+      const resultRecipients_elem: BlockchainAddress = _input.readAddress();
+      resultRecipients.push(resultRecipients_elem);
+    }
+    let pendingRecipient: Option<BlockchainAddress> = undefined;
+    const pendingRecipient_isSome = _input.readBoolean();
+    if (pendingRecipient_isSome) {
+      const pendingRecipient_option: BlockchainAddress = _input.readAddress();
+      pendingRecipient = pendingRecipient_option;
+    }
+    let resultAttestation: Option<ResultAttestation> = undefined;
+    const resultAttestation_isSome = _input.readBoolean();
+    if (resultAttestation_isSome) {
+      const resultAttestation_option: ResultAttestation = this.deserializeResultAttestation(_input);
+      resultAttestation = resultAttestation_option;
+    }
+    const participationMode: ParticipationMode = this.deserializeParticipationMode(_input);
+    const issuanceEnd: BN = _input.readU64();
+    const administratorShares: number = _input.readU32();
+    const approverShares: number = _input.readU32();
+    const anonymousVotes: number = _input.readU32();
+    const ballotMode: BallotMode = this.deserializeBallotMode(_input);
+    const drawContributors_vecLength = _input.readI32();
+    const drawContributors: BlockchainAddress[] = [];
+    for (let drawContributors_i = 0; drawContributors_i < drawContributors_vecLength; drawContributors_i++) {
+      const drawContributors_elem: BlockchainAddress = _input.readAddress();
+      drawContributors.push(drawContributors_elem);
+    }
+    let drawTranscript: Option<DrawTranscript> = undefined;
+    const drawTranscript_isSome = _input.readBoolean();
+    if (drawTranscript_isSome) {
+      const drawTranscript_option: DrawTranscript = this.deserializeDrawTranscript(_input);
+      drawTranscript = drawTranscript_option;
+    }
+    const runoffRule: RunoffRule = this.deserializeRunoffRule(_input);
+    let parentBallot: Option<BlockchainAddress> = undefined;
+    const parentBallot_isSome = _input.readBoolean();
+    if (parentBallot_isSome) {
+      const parentBallot_option: BlockchainAddress = _input.readAddress();
+      parentBallot = parentBallot_option;
+    }
+    let budgetOutcome: Option<BudgetOutcome> = undefined;
+    const budgetOutcome_isSome = _input.readBoolean();
+    if (budgetOutcome_isSome) {
+      const budgetOutcome_option: BudgetOutcome = this.deserializeBudgetOutcome(_input);
+      budgetOutcome = budgetOutcome_option;
+    }
+    let partialTally: Option<number> = undefined;
+    const partialTally_isSome = _input.readBoolean();
+    if (partialTally_isSome) {
+      const partialTally_option: number = _input.readU32();
+      partialTally = partialTally_option;
+    }
+    const foldingVotes_vecLength = _input.readI32();
+    const foldingVotes: number[] = [];
+    for (let foldingVotes_i = 0; foldingVotes_i < foldingVotes_vecLength; foldingVotes_i++) {
+      const foldingVotes_elem: number = _input.readU32();
+      foldingVotes.push(foldingVotes_elem);
+    }
+    const foldedVariables_vecLength = _input.readI32();
+    const foldedVariables: number[] = [];
+    for (let foldedVariables_i = 0; foldedVariables_i < foldedVariables_vecLength; foldedVariables_i++) {
+      const foldedVariables_elem: number = _input.readU32();
+      foldedVariables.push(foldedVariables_elem);
+    }
+    const pendingChecks_vecLength = _input.readI32();
+    const pendingChecks: number[] = [];
+    for (let pendingChecks_i = 0; pendingChecks_i < pendingChecks_vecLength; pendingChecks_i++) {
+      const pendingChecks_elem: number = _input.readU32();
+      pendingChecks.push(pendingChecks_elem);
+    }
+    const checkingVotes_vecLength = _input.readI32();
+    const checkingVotes: number[] = [];
+    for (let checkingVotes_i = 0; checkingVotes_i < checkingVotes_vecLength; checkingVotes_i++) {
+      const checkingVotes_elem: number = _input.readU32();
+      checkingVotes.push(checkingVotes_elem);
+    }
+    let checkStartedAt: Option<BN> = undefined;
+    const checkStartedAt_isSome = _input.readBoolean();
+    if (checkStartedAt_isSome) {
+      const checkStartedAt_option: BN = _input.readU64();
+      checkStartedAt = checkStartedAt_option;
+    }
+    const rejectedVoters_setLength = _input.readI32();
+    const rejectedVoters: BlockchainAddress[] = [];
+    for (let rejectedVoters_i = 0; rejectedVoters_i < rejectedVoters_setLength; rejectedVoters_i++) {
+      const rejectedVoters_elem: BlockchainAddress = _input.readAddress();
+      rejectedVoters.push(rejectedVoters_elem);
+    }
+    const roles: BallotRoles = this.deserializeBallotRoles(_input);
+    let cancellation: Option<CancellationProposal> = undefined;
+    const cancellation_isSome = _input.readBoolean();
+    if (cancellation_isSome) {
+      const cancellation_option: CancellationProposal = this.deserializeCancellationProposal(_input);
+      cancellation = cancellation_option;
     }
-    return { organization, administrator, title, description, options, startTime, endTime, status, tally, eligibleVoters, alreadyVoted, processState, processId, eventProcesses };
+    const approvalPolicy: ApprovalPolicy = this.deserializeApprovalPolicy(_input);
+    const pendingActions_vecLength = _input.readI32();
+    const pendingActions: PendingAction[] = [];
+    for (let pendingActions_i = 0; pendingActions_i < pendingActions_vecLength; pendingActions_i++) {
+      const pendingActions_elem: PendingAction = this.deserializePendingAction(_input);
+      pendingActions.push(pendingActions_elem);
+    }
+    return { stateVersion, organization, administrator, title, description, options, startTime, endTime, status, tally, voterRoot, voterCount, addedVoters, removedVoters, membershipPolicy, alreadyVoted, processState, processId, eventProcesses, processCounter, tallyStartedAt, tallyAttempts, failureReason, voteReceipts, talliedVariables, eventListener, pausedAt, metadataRevisions, attachments, eligibility, tokenBalances, resultDisclosure, privateResult, resultRecipients, pendingRecipient, resultAttestation, participationMode, issuanceEnd, administratorShares, approverShares, anonymousVotes, ballotMode, drawContributors, drawTranscript, runoffRule, parentBallot, budgetOutcome, partialTally, foldingVotes, foldedVariables, pendingChecks, checkingVotes, checkStartedAt, rejectedVoters, roles, cancellation, approvalPolicy, pendingActions };
   }
   public deserializeBallotStatus(_input: AbiInput): BallotStatus {
     const discriminant = _input.readU8();
@@ -93,6 +268,10 @@ export class BallotGenerated {
       return this.deserializeBallotStatusCompleted(_input);
     } else if (discriminant === 3) {
       return this.deserializeBallotStatusCancelled(_input);
+    } else if (discriminant === 4) {
+      return this.deserializeBallotStatusTallyFailed(_input);
+    } else if (discriminant === 5) {
+      return this.deserializeBallotStatusPaused(_input);
     }
     throw new Error("Unknown discriminant: " + discriminant);
   }
@@ -108,6 +287,12 @@ export class BallotGenerated {
   public deserializeBallotStatusCancelled(_input: AbiInput): BallotStatusCancelled {
     return { discriminant: BallotStatusD.Cancelled,  };
   }
+  public deserializeBallotStatusTallyFailed(_input: AbiInput): BallotStatusTallyFailed {
+    return { discriminant: BallotStatusD.TallyFailed,  };
+  }
+  public deserializeBallotStatusPaused(_input: AbiInput): BallotStatusPaused {
+    return { discriminant: BallotStatusD.Paused,  };
+  }
   public deserializeTally(_input: AbiInput): Tally {
     const option0: number = _input.readU32();
     const option1: number = _input.readU32();
@@ -117,6 +302,26 @@ export class BallotGenerated {
     const total: number = _input.readU32();
     return { option0, option1, option2, option3, option4, total };
   }
+  public deserializeMembershipPolicy(_input: AbiInput): MembershipPolicy {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeMembershipPolicySnapshot(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeMembershipPolicyAddOnly(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeMembershipPolicyLiveSync(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeMembershipPolicySnapshot(_input: AbiInput): MembershipPolicySnapshot {
+    return { discriminant: MembershipPolicyD.Snapshot,  };
+  }
+  public deserializeMembershipPolicyAddOnly(_input: AbiInput): MembershipPolicyAddOnly {
+    return { discriminant: MembershipPolicyD.AddOnly,  };
+  }
+  public deserializeMembershipPolicyLiveSync(_input: AbiInput): MembershipPolicyLiveSync {
+    return { discriminant: MembershipPolicyD.LiveSync,  };
+  }
   public deserializeBallotProcessState(_input: AbiInput): BallotProcessState {
     const discriminant = _input.readU8();
     if (discriminant === 0) {
@@ -127,6 +332,10 @@ export class BallotGenerated {
       return this.deserializeBallotProcessStateCompleted(_input);
     } else if (discriminant === 3) {
       return this.deserializeBallotProcessStateCancelled(_input);
+    } else if (discriminant === 4) {
+      return this.deserializeBallotProcessStateTallyFailed(_input);
+    } else if (discriminant === 5) {
+      return this.deserializeBallotProcessStatePaused(_input);
     }
     throw new Error("Unknown discriminant: " + discriminant);
   }
@@ -142,6 +351,35 @@ export class BallotGenerated {
   public deserializeBallotProcessStateCancelled(_input: AbiInput): BallotProcessStateCancelled {
     return { discriminant: BallotProcessStateD.Cancelled,  };
   }
+  public deserializeBallotProcessStateTallyFailed(_input: AbiInput): BallotProcessStateTallyFailed {
+    return { discriminant: BallotProcessStateD.TallyFailed,  };
+  }
+  public deserializeBallotProcessStatePaused(_input: AbiInput): BallotProcessStatePaused {
+    return { discriminant: BallotProcessStateD.Paused,  };
+  }
+  public deserializeProcessLog(_input: AbiInput): ProcessLog {
+    const records_mapLength = _input.readI32();
+    const records: Map<string, ProcessRecord> = new Map();
+    for (let records_i = 0; records_i < records_mapLength; records_i++) {
+      const records_key: string = _input.readString();
+      const records_value: ProcessRecord = this.deserializeProcessRecord(_input);
+      records.set(records_key, records_value);
+    }
+    const prunedCount: BN = _input.readU64();
+    const prunedFailures: BN = _input.readU64();
+    return { records, prunedCount, prunedFailures };
+  }
+  public deserializeProcessRecord(_input: AbiInput): ProcessRecord {
+    const state: ProcessState = this.deserializeProcessState(_input);
+    const timestamp: BN = _input.readU64();
+    let failureReason: Option<string> = undefined;
+    const failureReason_isSome = _input.readBoolean();
+    if (failureReason_isSome) {
+      const failureReason_option: string = _input.readString();
+      failureReason = failureReason_option;
+    }
+    return { state, timestamp, failureReason };
+  }
   public deserializeProcessState(_input: AbiInput): ProcessState {
     const discriminant = _input.readU8();
     if (discriminant === 0) {
@@ -150,6 +388,8 @@ export class BallotGenerated {
       return this.deserializeProcessStateComplete(_input);
     } else if (discriminant === 2) {
       return this.deserializeProcessStateIgnored(_input);
+    } else if (discriminant === 3) {
+      return this.deserializeProcessStateFailed(_input);
     }
     throw new Error("Unknown discriminant: " + discriminant);
   }
@@ -162,6 +402,288 @@ export class BallotGenerated {
   public deserializeProcessStateIgnored(_input: AbiInput): ProcessStateIgnored {
     return { discriminant: ProcessStateD.Ignored,  };
   }
+  public deserializeProcessStateFailed(_input: AbiInput): ProcessStateFailed {
+    return { discriminant: ProcessStateD.Failed,  };
+  }
+  public deserializeProcessCounter(_input: AbiInput): ProcessCounter {
+    const next: BN = _input.readU64();
+    return { next };
+  }
+  public deserializeVoteReceipt(_input: AbiInput): VoteReceipt {
+    const inputTransaction: Hash = _input.readHash();
+    let variableId: Option<number> = undefined;
+    const variableId_isSome = _input.readBoolean();
+    if (variableId_isSome) {
+      const variableId_option: number = _input.readU32();
+      variableId = variableId_option;
+    }
+    let commitment: Option<Hash> = undefined;
+    const commitment_isSome = _input.readBoolean();
+    if (commitment_isSome) {
+      const commitment_option: Hash = _input.readHash();
+      commitment = commitment_option;
+    }
+    const timestamp: BN = _input.readU64();
+    const metadataRevision: number = _input.readU32();
+    return { inputTransaction, variableId, commitment, timestamp, metadataRevision };
+  }
+  public deserializeMetadataRevision(_input: AbiInput): MetadataRevision {
+    const title: string = _input.readString();
+    const description: string = _input.readString();
+    const options_vecLength = _input.readI32();
+    const options: string[] = [];
+    for (let options_i = 0; options_i < options_vecLength; options_i++) {
+      const options_elem: string = _input.readString();
+      options.push(options_elem);
+    }
+    const contentHash: Hash = _input.readHash();
+    const timestamp: BN = _input.readU64();
+    return { title, description, options, contentHash, timestamp };
+  }
+  public deserializeAttachment(_input: AbiInput): Attachment {
+    const uri: string = _input.readString();
+    const sha256: string = _input.readString();
+    const mimeType: string = _input.readString();
+    return { uri, sha256, mimeType };
+  }
+  public deserializeEligibility(_input: AbiInput): Eligibility {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeEligibilityMembership(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeEligibilityTokenBalance(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeEligibilityMembership(_input: AbiInput): EligibilityMembership {
+    return { discriminant: EligibilityD.Membership,  };
+  }
+  public deserializeEligibilityTokenBalance(_input: AbiInput): EligibilityTokenBalance {
+    const token: BlockchainAddress = _input.readAddress();
+    const minBalance: BN = _input.readUnsignedBigInteger(16);
+    return { discriminant: EligibilityD.TokenBalance, token, minBalance };
+  }
+  public deserializeResultDisclosure(_input: AbiInput): ResultDisclosure {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeResultDisclosurePublic(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeResultDisclosureVoters(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeResultDisclosureAdministrators(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeResultDisclosurePublic(_input: AbiInput): ResultDisclosurePublic {
+    return { discriminant: ResultDisclosureD.Public,  };
+  }
+  public deserializeResultDisclosureVoters(_input: AbiInput): ResultDisclosureVoters {
+    return { discriminant: ResultDisclosureD.Voters,  };
+  }
+  public deserializeResultDisclosureAdministrators(_input: AbiInput): ResultDisclosureAdministrators {
+    const administrators_vecLength = _input.readI32();
+    const administrators: BlockchainAddress[] = [];
+    for (let administrators_i = 0; administrators_i < administrators_vecLength; administrators_i++) {
+      const administrators_elem: BlockchainAddress = _input.readAddress();
+      administrators.push(administrators_elem);
+    }
+    return { discriminant: ResultDisclosureD.Administrators, administrators };
+  }
+  public deserializePrivateResult(_input: AbiInput): PrivateResult {
+    const variableId: number = _input.readU32();
+    const inputsHash: Hash = _input.readHash();
+    const timestamp: BN = _input.readU64();
+    return { variableId, inputsHash, timestamp };
+  }
+  public deserializeResultAttestation(_input: AbiInput): ResultAttestation {
+    const data_vecLength = _input.readI32();
+    const data: Buffer = _input.readBytes(data_vecLength);
+    const signatures_vecLength = _input.readI32();
+    const signatures: Option<Signature>[] = [];
+    for (let signatures_i = 0; signatures_i < signatures_vecLength; signatures_i++) {
+      let signatures_elem: Option<Signature> = undefined;
+      const signatures_elem_isSome = _input.readBoolean();
+      if (signatures_elem_isSome) {
+        const signatures_elem_option: Signature = _input.readSignature();
+        signatures_elem = signatures_elem_option;
+      }
+      signatures.push(signatures_elem);
+    }
+    return { data, signatures };
+  }
+  public deserializeParticipationMode(_input: AbiInput): ParticipationMode {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeParticipationModePublic(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeParticipationModeAnonymous(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeParticipationModePublic(_input: AbiInput): ParticipationModePublic {
+    return { discriminant: ParticipationModeD.Public,  };
+  }
+  public deserializeParticipationModeAnonymous(_input: AbiInput): ParticipationModeAnonymous {
+    const issuanceSeconds: BN = _input.readU64();
+    return { discriminant: ParticipationModeD.Anonymous, issuanceSeconds };
+  }
+  public deserializeBallotMode(_input: AbiInput): BallotMode {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeBallotModeVote(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeBallotModeSortition(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeBallotModeBudgeting(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeBallotModeVote(_input: AbiInput): BallotModeVote {
+    return { discriminant: BallotModeD.Vote,  };
+  }
+  public deserializeBallotModeSortition(_input: AbiInput): BallotModeSortition {
+    const seats: number = _input.readU32();
+    return { discriminant: BallotModeD.Sortition, seats };
+  }
+  public deserializeBallotModeBudgeting(_input: AbiInput): BallotModeBudgeting {
+    const budget: number = _input.readU32();
+    const costs_vecLength = _input.readI32();
+    const costs: number[] = [];
+    for (let costs_i = 0; costs_i < costs_vecLength; costs_i++) {
+      const costs_elem: number = _input.readU32();
+      costs.push(costs_elem);
+    }
+    return { discriminant: BallotModeD.Budgeting, budget, costs };
+  }
+  public deserializeDrawTranscript(_input: AbiInput): DrawTranscript {
+    const voterRoot: Hash = _input.readHash();
+    const voterCount: number = _input.readU32();
+    const contributions_vecLength = _input.readI32();
+    const contributions: number[] = [];
+    for (let contributions_i = 0; contributions_i < contributions_vecLength; contributions_i++) {
+      const contributions_elem: number = _input.readU32();
+      contributions.push(contributions_elem);
+    }
+    const seed: BN = _input.readUnsignedBigInteger(16);
+    const selectedIndices_vecLength = _input.readI32();
+    const selectedIndices: number[] = [];
+    for (let selectedIndices_i = 0; selectedIndices_i < selectedIndices_vecLength; selectedIndices_i++) {
+      const selectedIndices_elem: number = _input.readU32();
+      selectedIndices.push(selectedIndices_elem);
+    }
+    const selected_vecLength = _input.readI32();
+    const selected: Option<BlockchainAddress>[] = [];
+    for (let selected_i = 0; selected_i < selected_vecLength; selected_i++) {
+      let selected_elem: Option<BlockchainAddress> = undefined;
+      const selected_elem_isSome = _input.readBoolean();
+      if (selected_elem_isSome) {
+        const selected_elem_option: BlockchainAddress = _input.readAddress();
+        selected_elem = selected_elem_option;
+      }
+      selected.push(selected_elem);
+    }
+    const timestamp: BN = _input.readU64();
+    return { voterRoot, voterCount, contributions, seed, selectedIndices, selected, timestamp };
+  }
+  public deserializeRunoffRule(_input: AbiInput): RunoffRule {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeRunoffRuleNone(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeRunoffRuleTopTwo(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeRunoffRuleNone(_input: AbiInput): RunoffRuleNone {
+    return { discriminant: RunoffRuleD.None,  };
+  }
+  public deserializeRunoffRuleTopTwo(_input: AbiInput): RunoffRuleTopTwo {
+    const durationSeconds: BN = _input.readU64();
+    return { discriminant: RunoffRuleD.TopTwo, durationSeconds };
+  }
+  public deserializeBudgetOutcome(_input: AbiInput): BudgetOutcome {
+    const points_vecLength = _input.readI32();
+    const points: number[] = [];
+    for (let points_i = 0; points_i < points_vecLength; points_i++) {
+      const points_elem: number = _input.readU32();
+      points.push(points_elem);
+    }
+    const funded_vecLength = _input.readI32();
+    const funded: string[] = [];
+    for (let funded_i = 0; funded_i < funded_vecLength; funded_i++) {
+      const funded_elem: string = _input.readString();
+      funded.push(funded_elem);
+    }
+    const spent: number = _input.readU32();
+    const timestamp: BN = _input.readU64();
+    return { points, funded, spent, timestamp };
+  }
+  public deserializeBallotRoles(_input: AbiInput): BallotRoles {
+    const talliers_vecLength = _input.readI32();
+    const talliers: BlockchainAddress[] = [];
+    for (let talliers_i = 0; talliers_i < talliers_vecLength; talliers_i++) {
+      const talliers_elem: BlockchainAddress = _input.readAddress();
+      talliers.push(talliers_elem);
+    }
+    const cancellers_vecLength = _input.readI32();
+    const cancellers: BlockchainAddress[] = [];
+    for (let cancellers_i = 0; cancellers_i < cancellers_vecLength; cancellers_i++) {
+      const cancellers_elem: BlockchainAddress = _input.readAddress();
+      cancellers.push(cancellers_elem);
+    }
+    const observers_vecLength = _input.readI32();
+    const observers: BlockchainAddress[] = [];
+    for (let observers_i = 0; observers_i < observers_vecLength; observers_i++) {
+      const observers_elem: BlockchainAddress = _input.readAddress();
+      observers.push(observers_elem);
+    }
+    return { talliers, cancellers, observers };
+  }
+  public deserializeCancellationProposal(_input: AbiInput): CancellationProposal {
+    const proposer: BlockchainAddress = _input.readAddress();
+    const proposedAt: BN = _input.readU64();
+    const expiresAt: BN = _input.readU64();
+    return { proposer, proposedAt, expiresAt };
+  }
+  public deserializeApprovalPolicy(_input: AbiInput): ApprovalPolicy {
+    const approvers_vecLength = _input.readI32();
+    const approvers: BlockchainAddress[] = [];
+    for (let approvers_i = 0; approvers_i < approvers_vecLength; approvers_i++) {
+      const approvers_elem: BlockchainAddress = _input.readAddress();
+      approvers.push(approvers_elem);
+    }
+    const threshold: number = _input.readU32();
+    const windowSeconds: BN = _input.readU64();
+    return { approvers, threshold, windowSeconds };
+  }
+  public deserializePendingAction(_input: AbiInput): PendingAction {
+    const action: BallotAction = this.deserializeBallotAction(_input);
+    const approvals_vecLength = _input.readI32();
+    const approvals: BlockchainAddress[] = [];
+    for (let approvals_i = 0; approvals_i < approvals_vecLength; approvals_i++) {
+      const approvals_elem: BlockchainAddress = _input.readAddress();
+      approvals.push(approvals_elem);
+    }
+    const expiresAt: BN = _input.readU64();
+    return { action, approvals, expiresAt };
+  }
+  public deserializeBallotAction(_input: AbiInput): BallotAction {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeBallotActionCancel(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeBallotActionSyncVoters(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeBallotActionCancel(_input: AbiInput): BallotActionCancel {
+    return { discriminant: BallotActionD.Cancel,  };
+  }
+  public deserializeBallotActionSyncVoters(_input: AbiInput): BallotActionSyncVoters {
+    const voterRoot: Hash = _input.readHash();
+    const voterCount: number = _input.readU32();
+    return { discriminant: BallotActionD.SyncVoters, voterRoot, voterCount };
+  }
   public deserializeOrganizationEvent(_input: AbiInput): OrganizationEvent {
     const discriminant = _input.readU8();
     if (discriminant === 0) {
@@ -228,6 +750,14 @@ export class BallotGenerated {
       return this.deserializeBallotEventTallyCompleted(_input);
     } else if (discriminant === 4) {
       return this.deserializeBallotEventStatusChanged(_input);
+    } else if (discriminant === 5) {
+      return this.deserializeBallotEventCommitteeDrawn(_input);
+    } else if (discriminant === 6) {
+      return this.deserializeBallotEventRunoffRequired(_input);
+    } else if (discriminant === 7) {
+      return this.deserializeBallotEventBudgetAllocated(_input);
+    } else if (discriminant === 8) {
+      return this.deserializeBallotEventVoteRejected(_input);
     }
     throw new Error("Unknown discriminant: " + discriminant);
   }
@@ -270,6 +800,62 @@ export class BallotGenerated {
     const processId: string = _input.readString();
     return { discriminant: BallotEventD.StatusChanged, status, timestamp, processId };
   }
+  public deserializeBallotEventCommitteeDrawn(_input: AbiInput): BallotEventCommitteeDrawn {
+    const selected_vecLength = _input.readI32();
+    const selected: BlockchainAddress[] = [];
+    for (let selected_i = 0; selected_i < selected_vecLength; selected_i++) {
+      const selected_elem: BlockchainAddress = _input.readAddress();
+      selected.push(selected_elem);
+    }
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.CommitteeDrawn, selected, timestamp, processId };
+  }
+  public deserializeBallotEventRunoffRequired(_input: AbiInput): BallotEventRunoffRequired {
+    const options_vecLength = _input.readI32();
+    const options: string[] = [];
+    for (let options_i = 0; options_i < options_vecLength; options_i++) {
+      const options_elem: string = _input.readString();
+      options.push(options_elem);
+    }
+    const voterRoot: Hash = _input.readHash();
+    const voterCount: number = _input.readU32();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.RunoffRequired, options, voterRoot, voterCount, timestamp, processId };
+  }
+  public deserializeBallotEventBudgetAllocated(_input: AbiInput): BallotEventBudgetAllocated {
+    const funded_vecLength = _input.readI32();
+    const funded: string[] = [];
+    for (let funded_i = 0; funded_i < funded_vecLength; funded_i++) {
+      const funded_elem: string = _input.readString();
+      funded.push(funded_elem);
+    }
+    const spent: number = _input.readU32();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.BudgetAllocated, funded, spent, timestamp, processId };
+  }
+  public deserializeBallotEventVoteRejected(_input: AbiInput): BallotEventVoteRejected {
+    const voter: BlockchainAddress = _input.readAddress();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.VoteRejected, voter, timestamp, processId };
+  }
+  public deserializeAnonymousBallot(_input: AbiInput): AnonymousBallot {
+    const administratorShare: BN = _input.readSignedBigInteger(16);
+    const approverShare: BN = _input.readSignedBigInteger(16);
+    const option: number = _input.readI8();
+    return { administratorShare, approverShare, option };
+  }
+  public deserializeBudgetAllocation(_input: AbiInput): BudgetAllocation {
+    const project0: number = _input.readI32();
+    const project1: number = _input.readI32();
+    const project2: number = _input.readI32();
+    const project3: number = _input.readI32();
+    const project4: number = _input.readI32();
+    return { project0, project1, project2, project3, project4 };
+  }
   public async getState(): Promise<BallotState> {
     const bytes = await this._client?.getContractStateBinary(this._address!);
     if (bytes === undefined) {
@@ -283,14 +869,126 @@ export class BallotGenerated {
     return { discriminant: "compute_tally",  };
   }
 
+  public deserializeRetryTallyAction(_input: AbiInput): RetryTallyAction {
+    return { discriminant: "retry_tally",  };
+  }
+
+  public deserializeAbandonTallyAction(_input: AbiInput): AbandonTallyAction {
+    const note: string = _input.readString();
+    return { discriminant: "abandon_tally", note };
+  }
+
+  public deserializeResetVoteCheckAction(_input: AbiInput): ResetVoteCheckAction {
+    const skip: boolean = _input.readBoolean();
+    return { discriminant: "reset_vote_check", skip };
+  }
+
   public deserializeSyncVotersAction(_input: AbiInput): SyncVotersAction {
-    const newEligibleVoters_vecLength = _input.readI32();
-    const newEligibleVoters: BlockchainAddress[] = [];
-    for (let newEligibleVoters_i = 0; newEligibleVoters_i < newEligibleVoters_vecLength; newEligibleVoters_i++) {
-      const newEligibleVoters_elem: BlockchainAddress = _input.readAddress();
-      newEligibleVoters.push(newEligibleVoters_elem);
+    const newVoterRoot: Hash = _input.readHash();
+    const newVoterCount: number = _input.readU32();
+    return { discriminant: "sync_voters", newVoterRoot, newVoterCount };
+  }
+
+  public deserializeSetEventListenerAction(_input: AbiInput): SetEventListenerAction {
+    let eventListener: Option<BlockchainAddress> = undefined;
+    const eventListener_isSome = _input.readBoolean();
+    if (eventListener_isSome) {
+      const eventListener_option: BlockchainAddress = _input.readAddress();
+      eventListener = eventListener_option;
+    }
+    return { discriminant: "set_event_listener", eventListener };
+  }
+
+  public deserializePauseBallotAction(_input: AbiInput): PauseBallotAction {
+    return { discriminant: "pause_ballot",  };
+  }
+
+  public deserializeResumeBallotAction(_input: AbiInput): ResumeBallotAction {
+    const extendEndTime: boolean = _input.readBoolean();
+    return { discriminant: "resume_ballot", extendEndTime };
+  }
+
+  public deserializeUpdateBallotMetadataAction(_input: AbiInput): UpdateBallotMetadataAction {
+    let title: Option<string> = undefined;
+    const title_isSome = _input.readBoolean();
+    if (title_isSome) {
+      const title_option: string = _input.readString();
+      title = title_option;
+    }
+    let description: Option<string> = undefined;
+    const description_isSome = _input.readBoolean();
+    if (description_isSome) {
+      const description_option: string = _input.readString();
+      description = description_option;
+    }
+    let options: Option<string[]> = undefined;
+    const options_isSome = _input.readBoolean();
+    if (options_isSome) {
+      const options_option_vecLength = _input.readI32();
+      const options_option: string[] = [];
+      for (let options_option_i = 0; options_option_i < options_option_vecLength; options_option_i++) {
+        const options_option_elem: string = _input.readString();
+        options_option.push(options_option_elem);
+      }
+      options = options_option;
     }
-    return { discriminant: "sync_voters", newEligibleVoters };
+    let costs: Option<number[]> = undefined;
+    const costs_isSome = _input.readBoolean();
+    if (costs_isSome) {
+      const costs_option_vecLength = _input.readI32();
+      const costs_option: number[] = [];
+      for (let costs_option_i = 0; costs_option_i < costs_option_vecLength; costs_option_i++) {
+        const costs_option_elem: number = _input.readU32();
+        costs_option.push(costs_option_elem);
+      }
+      costs = costs_option;
+    }
+    return { discriminant: "update_ballot_metadata", title, description, options, costs };
+  }
+
+  public deserializeCheckTokenEligibilityAction(_input: AbiInput): CheckTokenEligibilityAction {
+    return { discriminant: "check_token_eligibility",  };
+  }
+
+  public deserializePruneProcessesAction(_input: AbiInput): PruneProcessesAction {
+    const maxAgeSeconds: BN = _input.readU64();
+    return { discriminant: "prune_processes", maxAgeSeconds };
+  }
+
+  public deserializeRequestResultAction(_input: AbiInput): RequestResultAction {
+    const voterIndex: number = _input.readU32();
+    const membershipProof_vecLength = _input.readI32();
+    const membershipProof: Hash[] = [];
+    for (let membershipProof_i = 0; membershipProof_i < membershipProof_vecLength; membershipProof_i++) {
+      const membershipProof_elem: Hash = _input.readHash();
+      membershipProof.push(membershipProof_elem);
+    }
+    return { discriminant: "request_result", voterIndex, membershipProof };
+  }
+
+  public deserializeFoldVotesAction(_input: AbiInput): FoldVotesAction {
+    return { discriminant: "fold_votes",  };
+  }
+
+  public deserializeConfirmCancellationAction(_input: AbiInput): ConfirmCancellationAction {
+    return { discriminant: "confirm_cancellation",  };
+  }
+
+  public deserializeApproveActionAction(_input: AbiInput): ApproveActionAction {
+    const action: BallotAction = this.deserializeBallotAction(_input);
+    return { discriminant: "approve_action", action };
+  }
+
+  public deserializePublishDrawnMemberAction(_input: AbiInput): PublishDrawnMemberAction {
+    const seat: number = _input.readU32();
+    const member: BlockchainAddress = _input.readAddress();
+    const membershipProof_vecLength = _input.readI32();
+    const membershipProof: Hash[] = [];
+    for (let membershipProof_i = 0; membershipProof_i < membershipProof_vecLength; membershipProof_i++) {
+      const membershipProof_elem: Hash = _input.readHash();
+      membershipProof.push(membershipProof_elem);
+    }
+    return { discriminant: "publish_drawn_member", seat, member, membershipProof };
   }
 
   public deserializeCancelBallotAction(_input: AbiInput): CancelBallotAction {
@@ -307,6 +1005,11 @@ export class BallotGenerated {
     return { discriminant: "status_changed", event };
   }
 
+  public deserializeTokenBalanceCallbackCallback(_input: AbiInput): TokenBalanceCallbackCallback {
+    const voter: BlockchainAddress = _input.readAddress();
+    return { discriminant: "token_balance_callback", voter };
+  }
+
   public deserializeInitializeInit(_input: AbiInput): InitializeInit {
     const options_vecLength = _input.readI32();
     const options: string[] = [];
@@ -318,18 +1021,35 @@ export class BallotGenerated {
     const description: string = _input.readString();
     const organization: BlockchainAddress = _input.readAddress();
     const administrator: BlockchainAddress = _input.readAddress();
-    const eligibleVoters_vecLength = _input.readI32();
-    const eligibleVoters: BlockchainAddress[] = [];
-    for (let eligibleVoters_i = 0; eligibleVoters_i < eligibleVoters_vecLength; eligibleVoters_i++) {
-      const eligibleVoters_elem: BlockchainAddress = _input.readAddress();
-      eligibleVoters.push(eligibleVoters_elem);
-    }
+    const voterRoot: Hash = _input.readHash();
+    const voterCount: number = _input.readU32();
     const durationSeconds: BN = _input.readU64();
-    return { discriminant: "initialize", options, title, description, organization, administrator, eligibleVoters, durationSeconds };
+    const membershipPolicy: MembershipPolicy = this.deserializeMembershipPolicy(_input);
+    const attachments_vecLength = _input.readI32();
+    const attachments: Attachment[] = [];
+    for (let attachments_i = 0; attachments_i < attachments_vecLength; attachments_i++) {
+      const attachments_elem: Attachment = this.deserializeAttachment(_input);
+      attachments.push(attachments_elem);
+    }
+    const eligibility: Eligibility = this.deserializeEligibility(_input);
+    const resultDisclosure: ResultDisclosure = this.deserializeResultDisclosure(_input);
+    const participationMode: ParticipationMode = this.deserializeParticipationMode(_input);
+    const ballotMode: BallotMode = this.deserializeBallotMode(_input);
+    const runoffRule: RunoffRule = this.deserializeRunoffRule(_input);
+    let parentBallot: Option<BlockchainAddress> = undefined;
+    const parentBallot_isSome = _input.readBoolean();
+    if (parentBallot_isSome) {
+      const parentBallot_option: BlockchainAddress = _input.readAddress();
+      parentBallot = parentBallot_option;
+    }
+    const roles: BallotRoles = this.deserializeBallotRoles(_input);
+    const approvalPolicy: ApprovalPolicy = this.deserializeApprovalPolicy(_input);
+    return { discriminant: "initialize", options, title, description, organization, administrator, voterRoot, voterCount, durationSeconds, membershipPolicy, attachments, eligibility, resultDisclosure, participationMode, ballotMode, runoffRule, parentBallot, roles, approvalPolicy };
   }
 
 }
 export interface BallotState {
+  stateVersion: number;
   organization: BlockchainAddress;
   administrator: BlockchainAddress;
   title: string;
@@ -339,11 +1059,54 @@ export interface BallotState {
   endTime: BN;
   status: Option<BallotStatus>;
   tally: Option<Tally>;
-  eligibleVoters: BlockchainAddress[];
+  voterRoot: Hash;
+  voterCount: number;
+  addedVoters: BlockchainAddress[];
+  removedVoters: BlockchainAddress[];
+  membershipPolicy: MembershipPolicy;
   alreadyVoted: BlockchainAddress[];
   processState: BallotProcessState;
   processId: string;
-  eventProcesses: Map<string, ProcessState>;
+  eventProcesses: ProcessLog;
+  processCounter: ProcessCounter;
+  tallyStartedAt: Option<BN>;
+  tallyAttempts: number;
+  failureReason: Option<string>;
+  voteReceipts: Map<BlockchainAddress, VoteReceipt>;
+  talliedVariables: number[];
+  eventListener: Option<BlockchainAddress>;
+  pausedAt: Option<BN>;
+  metadataRevisions: MetadataRevision[];
+  attachments: Attachment[];
+  eligibility: Eligibility;
+  tokenBalances: Map<BlockchainAddress, BN>;
+  resultDisclosure: ResultDisclosure;
+  privateResult: Option<PrivateResult>;
+  resultRecipients: BlockchainAddress[];
+  pendingRecipient: Option<BlockchainAddress>;
+  resultAttestation: Option<ResultAttestation>;
+  participationMode: ParticipationMode;
+  issuanceEnd: BN;
+  administratorShares: number;
+  approverShares: number;
+  anonymousVotes: number;
+  ballotMode: BallotMode;
+  drawContributors: BlockchainAddress[];
+  drawTranscript: Option<DrawTranscript>;
+  runoffRule: RunoffRule;
+  parentBallot: Option<BlockchainAddress>;
+  budgetOutcome: Option<BudgetOutcome>;
+  partialTally: Option<number>;
+  foldingVotes: number[];
+  foldedVariables: number[];
+  pendingChecks: number[];
+  checkingVotes: number[];
+  checkStartedAt: Option<BN>;
+  rejectedVoters: BlockchainAddress[];
+  roles: BallotRoles;
+  cancellation: Option<CancellationProposal>;
+  approvalPolicy: ApprovalPolicy;
+  pendingActions: PendingAction[];
 }
 
 export enum BallotStatusD {
@@ -351,12 +1114,16 @@ export enum BallotStatusD {
   Tallying = 1,
   Completed = 2,
   Cancelled = 3,
+  TallyFailed = 4,
+  Paused = 5,
 }
 export type BallotStatus =
   | BallotStatusActive
   | BallotStatusTallying
   | BallotStatusCompleted
-  | BallotStatusCancelled;
+  | BallotStatusCancelled
+  | BallotStatusTallyFailed
+  | BallotStatusPaused;
 function serializeBallotStatus(out: AbiOutput, value: BallotStatus): void {
   if (value.discriminant === BallotStatusD.Active) {
     return serializeBallotStatusActive(out, value);
@@ -366,98 +1133,535 @@ function serializeBallotStatus(out: AbiOutput, value: BallotStatus): void {
     return serializeBallotStatusCompleted(out, value);
   } else if (value.discriminant === BallotStatusD.Cancelled) {
     return serializeBallotStatusCancelled(out, value);
+  } else if (value.discriminant === BallotStatusD.TallyFailed) {
+    return serializeBallotStatusTallyFailed(out, value);
+  } else if (value.discriminant === BallotStatusD.Paused) {
+    return serializeBallotStatusPaused(out, value);
+  }
+}
+
+export interface BallotStatusActive {
+  discriminant: BallotStatusD.Active;
+}
+function serializeBallotStatusActive(_out: AbiOutput, _value: BallotStatusActive): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusTallying {
+  discriminant: BallotStatusD.Tallying;
+}
+function serializeBallotStatusTallying(_out: AbiOutput, _value: BallotStatusTallying): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusCompleted {
+  discriminant: BallotStatusD.Completed;
+}
+function serializeBallotStatusCompleted(_out: AbiOutput, _value: BallotStatusCompleted): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusCancelled {
+  discriminant: BallotStatusD.Cancelled;
+}
+function serializeBallotStatusCancelled(_out: AbiOutput, _value: BallotStatusCancelled): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusTallyFailed {
+  discriminant: BallotStatusD.TallyFailed;
+}
+function serializeBallotStatusTallyFailed(_out: AbiOutput, _value: BallotStatusTallyFailed): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusPaused {
+  discriminant: BallotStatusD.Paused;
+}
+function serializeBallotStatusPaused(_out: AbiOutput, _value: BallotStatusPaused): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface Tally {
+  option0: number;
+  option1: number;
+  option2: number;
+  option3: number;
+  option4: number;
+  total: number;
+}
+
+export enum MembershipPolicyD {
+  Snapshot = 0,
+  AddOnly = 1,
+  LiveSync = 2,
+}
+export type MembershipPolicy =
+  | MembershipPolicySnapshot
+  | MembershipPolicyAddOnly
+  | MembershipPolicyLiveSync;
+function serializeMembershipPolicy(out: AbiOutput, value: MembershipPolicy): void {
+  if (value.discriminant === MembershipPolicyD.Snapshot) {
+    return serializeMembershipPolicySnapshot(out, value);
+  } else if (value.discriminant === MembershipPolicyD.AddOnly) {
+    return serializeMembershipPolicyAddOnly(out, value);
+  } else if (value.discriminant === MembershipPolicyD.LiveSync) {
+    return serializeMembershipPolicyLiveSync(out, value);
+  }
+}
+
+export interface MembershipPolicySnapshot {
+  discriminant: MembershipPolicyD.Snapshot;
+}
+function serializeMembershipPolicySnapshot(_out: AbiOutput, _value: MembershipPolicySnapshot): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface MembershipPolicyAddOnly {
+  discriminant: MembershipPolicyD.AddOnly;
+}
+function serializeMembershipPolicyAddOnly(_out: AbiOutput, _value: MembershipPolicyAddOnly): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface MembershipPolicyLiveSync {
+  discriminant: MembershipPolicyD.LiveSync;
+}
+function serializeMembershipPolicyLiveSync(_out: AbiOutput, _value: MembershipPolicyLiveSync): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export enum BallotProcessStateD {
+  Active = 0,
+  Tallying = 1,
+  Completed = 2,
+  Cancelled = 3,
+  TallyFailed = 4,
+  Paused = 5,
+}
+export type BallotProcessState =
+  | BallotProcessStateActive
+  | BallotProcessStateTallying
+  | BallotProcessStateCompleted
+  | BallotProcessStateCancelled
+  | BallotProcessStateTallyFailed
+  | BallotProcessStatePaused;
+
+export interface BallotProcessStateActive {
+  discriminant: BallotProcessStateD.Active;
+}
+
+export interface BallotProcessStateTallying {
+  discriminant: BallotProcessStateD.Tallying;
+}
+
+export interface BallotProcessStateCompleted {
+  discriminant: BallotProcessStateD.Completed;
+}
+
+export interface BallotProcessStateCancelled {
+  discriminant: BallotProcessStateD.Cancelled;
+}
+
+export interface BallotProcessStateTallyFailed {
+  discriminant: BallotProcessStateD.TallyFailed;
+}
+
+export interface BallotProcessStatePaused {
+  discriminant: BallotProcessStateD.Paused;
+}
+
+export interface ProcessLog {
+  records: Map<string, ProcessRecord>;
+  prunedCount: BN;
+  prunedFailures: BN;
+}
+
+export interface ProcessRecord {
+  state: ProcessState;
+  timestamp: BN;
+  failureReason: Option<string>;
+}
+
+export enum ProcessStateD {
+  Received = 0,
+  Complete = 1,
+  Ignored = 2,
+  Failed = 3,
+}
+export type ProcessState =
+  | ProcessStateReceived
+  | ProcessStateComplete
+  | ProcessStateIgnored
+  | ProcessStateFailed;
+
+export interface ProcessStateReceived {
+  discriminant: ProcessStateD.Received;
+}
+
+export interface ProcessStateComplete {
+  discriminant: ProcessStateD.Complete;
+}
+
+export interface ProcessStateIgnored {
+  discriminant: ProcessStateD.Ignored;
+}
+
+export interface ProcessStateFailed {
+  discriminant: ProcessStateD.Failed;
+}
+
+export interface ProcessCounter {
+  next: BN;
+}
+
+export interface VoteReceipt {
+  inputTransaction: Hash;
+  variableId: Option<number>;
+  commitment: Option<Hash>;
+  timestamp: BN;
+  metadataRevision: number;
+}
+
+export interface MetadataRevision {
+  title: string;
+  description: string;
+  options: string[];
+  contentHash: Hash;
+  timestamp: BN;
+}
+
+export interface Attachment {
+  uri: string;
+  sha256: string;
+  mimeType: string;
+}
+function serializeAttachment(_out: AbiOutput, _value: Attachment): void {
+  const {uri, sha256, mimeType} = _value;
+  _out.writeString(uri);
+  _out.writeString(sha256);
+  _out.writeString(mimeType);
+}
+
+export enum EligibilityD {
+  Membership = 0,
+  TokenBalance = 1,
+}
+export type Eligibility =
+  | EligibilityMembership
+  | EligibilityTokenBalance;
+function serializeEligibility(out: AbiOutput, value: Eligibility): void {
+  if (value.discriminant === EligibilityD.Membership) {
+    return serializeEligibilityMembership(out, value);
+  } else if (value.discriminant === EligibilityD.TokenBalance) {
+    return serializeEligibilityTokenBalance(out, value);
+  }
+}
+
+export interface EligibilityMembership {
+  discriminant: EligibilityD.Membership;
+}
+function serializeEligibilityMembership(_out: AbiOutput, _value: EligibilityMembership): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface EligibilityTokenBalance {
+  discriminant: EligibilityD.TokenBalance;
+  token: BlockchainAddress;
+  minBalance: BN;
+}
+function serializeEligibilityTokenBalance(_out: AbiOutput, _value: EligibilityTokenBalance): void {
+  const {token, minBalance} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeAddress(token);
+  _out.writeUnsignedBigInteger(minBalance, 16);
+}
+
+export enum ResultDisclosureD {
+  Public = 0,
+  Voters = 1,
+  Administrators = 2,
+}
+export type ResultDisclosure =
+  | ResultDisclosurePublic
+  | ResultDisclosureVoters
+  | ResultDisclosureAdministrators;
+function serializeResultDisclosure(out: AbiOutput, value: ResultDisclosure): void {
+  if (value.discriminant === ResultDisclosureD.Public) {
+    return serializeResultDisclosurePublic(out, value);
+  } else if (value.discriminant === ResultDisclosureD.Voters) {
+    return serializeResultDisclosureVoters(out, value);
+  } else if (value.discriminant === ResultDisclosureD.Administrators) {
+    return serializeResultDisclosureAdministrators(out, value);
+  }
+}
+
+export interface ResultDisclosurePublic {
+  discriminant: ResultDisclosureD.Public;
+}
+function serializeResultDisclosurePublic(_out: AbiOutput, _value: ResultDisclosurePublic): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ResultDisclosureVoters {
+  discriminant: ResultDisclosureD.Voters;
+}
+function serializeResultDisclosureVoters(_out: AbiOutput, _value: ResultDisclosureVoters): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ResultDisclosureAdministrators {
+  discriminant: ResultDisclosureD.Administrators;
+  administrators: BlockchainAddress[];
+}
+function serializeResultDisclosureAdministrators(_out: AbiOutput, _value: ResultDisclosureAdministrators): void {
+  const {administrators} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(administrators.length);
+  for (const administrators_vec of administrators) {
+    _out.writeAddress(administrators_vec);
+  }
+}
+
+export interface PrivateResult {
+  variableId: number;
+  inputsHash: Hash;
+  timestamp: BN;
+}
+
+export interface ResultAttestation {
+  data: Buffer;
+  signatures: Option<Signature>[];
+}
+
+export enum ParticipationModeD {
+  Public = 0,
+  Anonymous = 1,
+}
+export type ParticipationMode =
+  | ParticipationModePublic
+  | ParticipationModeAnonymous;
+function serializeParticipationMode(out: AbiOutput, value: ParticipationMode): void {
+  if (value.discriminant === ParticipationModeD.Public) {
+    return serializeParticipationModePublic(out, value);
+  } else if (value.discriminant === ParticipationModeD.Anonymous) {
+    return serializeParticipationModeAnonymous(out, value);
+  }
+}
+
+export interface ParticipationModePublic {
+  discriminant: ParticipationModeD.Public;
+}
+function serializeParticipationModePublic(_out: AbiOutput, _value: ParticipationModePublic): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ParticipationModeAnonymous {
+  discriminant: ParticipationModeD.Anonymous;
+  issuanceSeconds: BN;
+}
+function serializeParticipationModeAnonymous(_out: AbiOutput, _value: ParticipationModeAnonymous): void {
+  const {issuanceSeconds} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU64(issuanceSeconds);
+}
+
+export enum BallotModeD {
+  Vote = 0,
+  Sortition = 1,
+  Budgeting = 2,
+}
+export type BallotMode =
+  | BallotModeVote
+  | BallotModeSortition
+  | BallotModeBudgeting;
+function serializeBallotMode(out: AbiOutput, value: BallotMode): void {
+  if (value.discriminant === BallotModeD.Vote) {
+    return serializeBallotModeVote(out, value);
+  } else if (value.discriminant === BallotModeD.Sortition) {
+    return serializeBallotModeSortition(out, value);
+  } else if (value.discriminant === BallotModeD.Budgeting) {
+    return serializeBallotModeBudgeting(out, value);
   }
 }
 
-export interface BallotStatusActive {
-  discriminant: BallotStatusD.Active;
+export interface BallotModeVote {
+  discriminant: BallotModeD.Vote;
 }
-function serializeBallotStatusActive(_out: AbiOutput, _value: BallotStatusActive): void {
+function serializeBallotModeVote(_out: AbiOutput, _value: BallotModeVote): void {
   const {} = _value;
   _out.writeU8(_value.discriminant);
 }
 
-export interface BallotStatusTallying {
-  discriminant: BallotStatusD.Tallying;
+export interface BallotModeSortition {
+  discriminant: BallotModeD.Sortition;
+  seats: number;
 }
-function serializeBallotStatusTallying(_out: AbiOutput, _value: BallotStatusTallying): void {
-  const {} = _value;
+function serializeBallotModeSortition(_out: AbiOutput, _value: BallotModeSortition): void {
+  const {seats} = _value;
   _out.writeU8(_value.discriminant);
+  _out.writeU32(seats);
 }
 
-export interface BallotStatusCompleted {
-  discriminant: BallotStatusD.Completed;
+export interface BallotModeBudgeting {
+  discriminant: BallotModeD.Budgeting;
+  budget: number;
+  costs: number[];
 }
-function serializeBallotStatusCompleted(_out: AbiOutput, _value: BallotStatusCompleted): void {
-  const {} = _value;
+function serializeBallotModeBudgeting(_out: AbiOutput, _value: BallotModeBudgeting): void {
+  const {budget, costs} = _value;
   _out.writeU8(_value.discriminant);
+  _out.writeU32(budget);
+  _out.writeI32(costs.length);
+  for (const costs_vec of costs) {
+    _out.writeU32(costs_vec);
+  }
 }
 
-export interface BallotStatusCancelled {
-  discriminant: BallotStatusD.Cancelled;
+export interface DrawTranscript {
+  voterRoot: Hash;
+  voterCount: number;
+  contributions: number[];
+  seed: BN;
+  selectedIndices: number[];
+  selected: Option<BlockchainAddress>[];
+  timestamp: BN;
 }
-function serializeBallotStatusCancelled(_out: AbiOutput, _value: BallotStatusCancelled): void {
+
+export enum RunoffRuleD {
+  None = 0,
+  TopTwo = 1,
+}
+export type RunoffRule =
+  | RunoffRuleNone
+  | RunoffRuleTopTwo;
+function serializeRunoffRule(out: AbiOutput, value: RunoffRule): void {
+  if (value.discriminant === RunoffRuleD.None) {
+    return serializeRunoffRuleNone(out, value);
+  } else if (value.discriminant === RunoffRuleD.TopTwo) {
+    return serializeRunoffRuleTopTwo(out, value);
+  }
+}
+
+export interface RunoffRuleNone {
+  discriminant: RunoffRuleD.None;
+}
+function serializeRunoffRuleNone(_out: AbiOutput, _value: RunoffRuleNone): void {
   const {} = _value;
   _out.writeU8(_value.discriminant);
 }
 
-export interface Tally {
-  option0: number;
-  option1: number;
-  option2: number;
-  option3: number;
-  option4: number;
-  total: number;
+export interface RunoffRuleTopTwo {
+  discriminant: RunoffRuleD.TopTwo;
+  durationSeconds: BN;
 }
-
-export enum BallotProcessStateD {
-  Active = 0,
-  Tallying = 1,
-  Completed = 2,
-  Cancelled = 3,
+function serializeRunoffRuleTopTwo(_out: AbiOutput, _value: RunoffRuleTopTwo): void {
+  const {durationSeconds} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU64(durationSeconds);
 }
-export type BallotProcessState =
-  | BallotProcessStateActive
-  | BallotProcessStateTallying
-  | BallotProcessStateCompleted
-  | BallotProcessStateCancelled;
 
-export interface BallotProcessStateActive {
-  discriminant: BallotProcessStateD.Active;
+export interface BudgetOutcome {
+  points: number[];
+  funded: string[];
+  spent: number;
+  timestamp: BN;
 }
 
-export interface BallotProcessStateTallying {
-  discriminant: BallotProcessStateD.Tallying;
+export interface BallotRoles {
+  talliers: BlockchainAddress[];
+  cancellers: BlockchainAddress[];
+  observers: BlockchainAddress[];
+}
+function serializeBallotRoles(_out: AbiOutput, _value: BallotRoles): void {
+  const {talliers, cancellers, observers} = _value;
+  _out.writeI32(talliers.length);
+  for (const talliers_vec of talliers) {
+    _out.writeAddress(talliers_vec);
+  }
+  _out.writeI32(cancellers.length);
+  for (const cancellers_vec of cancellers) {
+    _out.writeAddress(cancellers_vec);
+  }
+  _out.writeI32(observers.length);
+  for (const observers_vec of observers) {
+    _out.writeAddress(observers_vec);
+  }
 }
 
-export interface BallotProcessStateCompleted {
-  discriminant: BallotProcessStateD.Completed;
+export interface CancellationProposal {
+  proposer: BlockchainAddress;
+  proposedAt: BN;
+  expiresAt: BN;
 }
 
-export interface BallotProcessStateCancelled {
-  discriminant: BallotProcessStateD.Cancelled;
+export interface ApprovalPolicy {
+  approvers: BlockchainAddress[];
+  threshold: number;
+  windowSeconds: BN;
+}
+function serializeApprovalPolicy(_out: AbiOutput, _value: ApprovalPolicy): void {
+  const {approvers, threshold, windowSeconds} = _value;
+  _out.writeI32(approvers.length);
+  for (const approvers_vec of approvers) {
+    _out.writeAddress(approvers_vec);
+  }
+  _out.writeU32(threshold);
+  _out.writeU64(windowSeconds);
 }
 
-export enum ProcessStateD {
-  Received = 0,
-  Complete = 1,
-  Ignored = 2,
+export interface PendingAction {
+  action: BallotAction;
+  approvals: BlockchainAddress[];
+  expiresAt: BN;
 }
-export type ProcessState =
-  | ProcessStateReceived
-  | ProcessStateComplete
-  | ProcessStateIgnored;
 
-export interface ProcessStateReceived {
-  discriminant: ProcessStateD.Received;
+export enum BallotActionD {
+  Cancel = 0,
+  SyncVoters = 1,
+}
+export type BallotAction =
+  | BallotActionCancel
+  | BallotActionSyncVoters;
+function serializeBallotAction(out: AbiOutput, value: BallotAction): void {
+  if (value.discriminant === BallotActionD.Cancel) {
+    return serializeBallotActionCancel(out, value);
+  } else if (value.discriminant === BallotActionD.SyncVoters) {
+    return serializeBallotActionSyncVoters(out, value);
+  }
 }
 
-export interface ProcessStateComplete {
-  discriminant: ProcessStateD.Complete;
+export interface BallotActionCancel {
+  discriminant: BallotActionD.Cancel;
+}
+function serializeBallotActionCancel(_out: AbiOutput, _value: BallotActionCancel): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
 }
 
-export interface ProcessStateIgnored {
-  discriminant: ProcessStateD.Ignored;
+export interface BallotActionSyncVoters {
+  discriminant: BallotActionD.SyncVoters;
+  voterRoot: Hash;
+  voterCount: number;
+}
+function serializeBallotActionSyncVoters(_out: AbiOutput, _value: BallotActionSyncVoters): void {
+  const {voterRoot, voterCount} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeHash(voterRoot);
+  _out.writeU32(voterCount);
 }
 
 export enum OrganizationEventD {
@@ -565,13 +1769,21 @@ export enum BallotEventD {
   TallyStarted = 2,
   TallyCompleted = 3,
   StatusChanged = 4,
+  CommitteeDrawn = 5,
+  RunoffRequired = 6,
+  BudgetAllocated = 7,
+  VoteRejected = 8,
 }
 export type BallotEvent =
   | BallotEventMembersUpdated
   | BallotEventVoteCast
   | BallotEventTallyStarted
   | BallotEventTallyCompleted
-  | BallotEventStatusChanged;
+  | BallotEventStatusChanged
+  | BallotEventCommitteeDrawn
+  | BallotEventRunoffRequired
+  | BallotEventBudgetAllocated
+  | BallotEventVoteRejected;
 function serializeBallotEvent(out: AbiOutput, value: BallotEvent): void {
   if (value.discriminant === BallotEventD.MembersUpdated) {
     return serializeBallotEventMembersUpdated(out, value);
@@ -583,6 +1795,14 @@ function serializeBallotEvent(out: AbiOutput, value: BallotEvent): void {
     return serializeBallotEventTallyCompleted(out, value);
   } else if (value.discriminant === BallotEventD.StatusChanged) {
     return serializeBallotEventStatusChanged(out, value);
+  } else if (value.discriminant === BallotEventD.CommitteeDrawn) {
+    return serializeBallotEventCommitteeDrawn(out, value);
+  } else if (value.discriminant === BallotEventD.RunoffRequired) {
+    return serializeBallotEventRunoffRequired(out, value);
+  } else if (value.discriminant === BallotEventD.BudgetAllocated) {
+    return serializeBallotEventBudgetAllocated(out, value);
+  } else if (value.discriminant === BallotEventD.VoteRejected) {
+    return serializeBallotEventVoteRejected(out, value);
   }
 }
 
@@ -660,7 +1880,106 @@ function serializeBallotEventStatusChanged(_out: AbiOutput, _value: BallotEventS
   _out.writeString(processId);
 }
 
-export function initialize(options: string[], title: string, description: string, organization: BlockchainAddress, administrator: BlockchainAddress, eligibleVoters: BlockchainAddress[], durationSeconds: BN): Buffer {
+export interface BallotEventCommitteeDrawn {
+  discriminant: BallotEventD.CommitteeDrawn;
+  selected: BlockchainAddress[];
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventCommitteeDrawn(_out: AbiOutput, _value: BallotEventCommitteeDrawn): void {
+  const {selected, timestamp, processId} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(selected.length);
+  for (const selected_vec of selected) {
+    _out.writeAddress(selected_vec);
+  }
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventRunoffRequired {
+  discriminant: BallotEventD.RunoffRequired;
+  options: string[];
+  voterRoot: Hash;
+  voterCount: number;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventRunoffRequired(_out: AbiOutput, _value: BallotEventRunoffRequired): void {
+  const {options, voterRoot, voterCount, timestamp, processId} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(options.length);
+  for (const options_vec of options) {
+    _out.writeString(options_vec);
+  }
+  _out.writeHash(voterRoot);
+  _out.writeU32(voterCount);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventBudgetAllocated {
+  discriminant: BallotEventD.BudgetAllocated;
+  funded: string[];
+  spent: number;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventBudgetAllocated(_out: AbiOutput, _value: BallotEventBudgetAllocated): void {
+  const {funded, spent, timestamp, processId} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(funded.length);
+  for (const funded_vec of funded) {
+    _out.writeString(funded_vec);
+  }
+  _out.writeU32(spent);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventVoteRejected {
+  discriminant: BallotEventD.VoteRejected;
+  voter: BlockchainAddress;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventVoteRejected(_out: AbiOutput, _value: BallotEventVoteRejected): void {
+  const {voter, timestamp, processId} = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeAddress(voter);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface AnonymousBallot {
+  administratorShare: BN;
+  approverShare: BN;
+  option: number;
+}
+function serializeAnonymousBallot(_out: AbiOutput, _value: AnonymousBallot): void {
+  const {administratorShare, approverShare, option} = _value;
+  _out.writeSignedBigInteger(administratorShare, 16);
+  _out.writeSignedBigInteger(approverShare, 16);
+  _out.writeI8(option);
+}
+
+export interface BudgetAllocation {
+  project0: number;
+  project1: number;
+  project2: number;
+  project3: number;
+  project4: number;
+}
+function serializeBudgetAllocation(_out: AbiOutput, _value: BudgetAllocation): void {
+  const {project0, project1, project2, project3, project4} = _value;
+  _out.writeI32(project0);
+  _out.writeI32(project1);
+  _out.writeI32(project2);
+  _out.writeI32(project3);
+  _out.writeI32(project4);
+}
+
+export function initialize(options: string[], title: string, description: string, organization: BlockchainAddress, administrator: BlockchainAddress, voterRoot: Hash, voterCount: number, durationSeconds: BN, membershipPolicy: MembershipPolicy, attachments: Attachment[], eligibility: Eligibility, resultDisclosure: ResultDisclosure, participationMode: ParticipationMode, ballotMode: BallotMode, runoffRule: RunoffRule, parentBallot: Option<BlockchainAddress>, roles: BallotRoles, approvalPolicy: ApprovalPolicy): Buffer {
   return AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeBytes(Buffer.from("ffffffff0f", "hex"));
     _out.writeI32(options.length);
@@ -671,11 +1990,25 @@ export function initialize(options: string[], title: string, description: string
     _out.writeString(description);
     _out.writeAddress(organization);
     _out.writeAddress(administrator);
-    _out.writeI32(eligibleVoters.length);
-    for (const eligibleVoters_vec of eligibleVoters) {
-      _out.writeAddress(eligibleVoters_vec);
-    }
+    _out.writeHash(voterRoot);
+    _out.writeU32(voterCount);
     _out.writeU64(durationSeconds);
+    serializeMembershipPolicy(_out, membershipPolicy);
+    _out.writeI32(attachments.length);
+    for (const attachments_vec of attachments) {
+      serializeAttachment(_out, attachments_vec);
+    }
+    serializeEligibility(_out, eligibility);
+    serializeResultDisclosure(_out, resultDisclosure);
+    serializeParticipationMode(_out, participationMode);
+    serializeBallotMode(_out, ballotMode);
+    serializeRunoffRule(_out, runoffRule);
+    _out.writeBoolean(parentBallot !== undefined);
+    if (parentBallot !== undefined) {
+      _out.writeAddress(parentBallot);
+    }
+    serializeBallotRoles(_out, roles);
+    serializeApprovalPolicy(_out, approvalPolicy);
   });
 }
 
@@ -686,13 +2019,151 @@ export function computeTally(): Buffer {
   });
 }
 
-export function syncVoters(newEligibleVoters: BlockchainAddress[]): Buffer {
+export function retryTally(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("02", "hex"));
+  });
+}
+
+export function abandonTally(note: string): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("03", "hex"));
+    _out.writeString(note);
+  });
+}
+
+export function resetVoteCheck(skip: boolean): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("04", "hex"));
+    _out.writeBoolean(skip);
+  });
+}
+
+export function syncVoters(newVoterRoot: Hash, newVoterCount: number): Buffer {
   return AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeU8(0x09);
     _out.writeBytes(Buffer.from("05", "hex"));
-    _out.writeI32(newEligibleVoters.length);
-    for (const newEligibleVoters_vec of newEligibleVoters) {
-      _out.writeAddress(newEligibleVoters_vec);
+    _out.writeHash(newVoterRoot);
+    _out.writeU32(newVoterCount);
+  });
+}
+
+export function setEventListener(eventListener: Option<BlockchainAddress>): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("06", "hex"));
+    _out.writeBoolean(eventListener !== undefined);
+    if (eventListener !== undefined) {
+      _out.writeAddress(eventListener);
+    }
+  });
+}
+
+export function pauseBallot(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("07", "hex"));
+  });
+}
+
+export function resumeBallot(extendEndTime: boolean): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("08", "hex"));
+    _out.writeBoolean(extendEndTime);
+  });
+}
+
+export function updateBallotMetadata(title: Option<string>, description: Option<string>, options: Option<string[]>, costs: Option<number[]>): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("09", "hex"));
+    _out.writeBoolean(title !== undefined);
+    if (title !== undefined) {
+      _out.writeString(title);
+    }
+    _out.writeBoolean(description !== undefined);
+    if (description !== undefined) {
+      _out.writeString(description);
+    }
+    _out.writeBoolean(options !== undefined);
+    if (options !== undefined) {
+      _out.writeI32(options.length);
+      for (const options_vec of options) {
+        _out.writeString(options_vec);
+      }
+    }
+    _out.writeBoolean(costs !== undefined);
+    if (costs !== undefined) {
+      _out.writeI32(costs.length);
+      for (const costs_vec of costs) {
+        _out.writeU32(costs_vec);
+      }
+    }
+  });
+}
+
+export function checkTokenEligibility(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0a", "hex"));
+  });
+}
+
+export function pruneProcesses(maxAgeSeconds: BN): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0b", "hex"));
+    _out.writeU64(maxAgeSeconds);
+  });
+}
+
+export function requestResult(voterIndex: number, membershipProof: Hash[]): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0c", "hex"));
+    _out.writeU32(voterIndex);
+    _out.writeI32(membershipProof.length);
+    for (const membershipProof_vec of membershipProof) {
+      _out.writeHash(membershipProof_vec);
+    }
+  });
+}
+
+export function foldVotes(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0d", "hex"));
+  });
+}
+
+export function confirmCancellation(): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0e", "hex"));
+  });
+}
+
+export function approveAction(action: BallotAction): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("0f", "hex"));
+    serializeBallotAction(_out, action);
+  });
+}
+
+export function publishDrawnMember(seat: number, member: BlockchainAddress, membershipProof: Hash[]): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeU8(0x09);
+    _out.writeBytes(Buffer.from("10", "hex"));
+    _out.writeU32(seat);
+    _out.writeAddress(member);
+    _out.writeI32(membershipProof.length);
+    for (const membershipProof_vec of membershipProof) {
+      _out.writeHash(membershipProof_vec);
     }
   });
 }
@@ -720,9 +2191,14 @@ export function statusChanged(event: BallotEvent): Buffer {
   });
 }
 
-export function castVote(): SecretInputBuilder<number> {
+export function castVote(voterIndex: number, membershipProof: Hash[]): SecretInputBuilder<number> {
   const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeBytes(Buffer.from("60", "hex"));
+    _out.writeU32(voterIndex);
+    _out.writeI32(membershipProof.length);
+    for (const membershipProof_vec of membershipProof) {
+      _out.writeHash(membershipProof_vec);
+    }
   });
   const _secretInput = (secret_input_lambda: number): CompactBitArray => AbiBitOutput.serialize((_out) => {
     _out.writeI8(secret_input_lambda);
@@ -730,6 +2206,62 @@ export function castVote(): SecretInputBuilder<number> {
   return new SecretInputBuilder(_publicRpc, _secretInput);
 }
 
+export function issueTokenShare(voter: BlockchainAddress, voterIndex: number, membershipProof: Hash[]): SecretInputBuilder<BN> {
+  const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("64", "hex"));
+    _out.writeAddress(voter);
+    _out.writeU32(voterIndex);
+    _out.writeI32(membershipProof.length);
+    for (const membershipProof_vec of membershipProof) {
+      _out.writeHash(membershipProof_vec);
+    }
+  });
+  const _secretInput = (secret_input_lambda: BN): CompactBitArray => AbiBitOutput.serialize((_out) => {
+    _out.writeSignedBigInteger(secret_input_lambda, 16);
+  });
+  return new SecretInputBuilder(_publicRpc, _secretInput);
+}
+
+export function castAnonymousVote(): SecretInputBuilder<AnonymousBallot> {
+  const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("65", "hex"));
+  });
+  const _secretInput = (secret_input_lambda: AnonymousBallot): CompactBitArray => AbiBitOutput.serialize((_out) => {
+    serializeAnonymousBallot(_out, secret_input_lambda);
+  });
+  return new SecretInputBuilder(_publicRpc, _secretInput);
+}
+
+export function contributeRandomness(voterIndex: number, membershipProof: Hash[]): SecretInputBuilder<BN> {
+  const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("66", "hex"));
+    _out.writeU32(voterIndex);
+    _out.writeI32(membershipProof.length);
+    for (const membershipProof_vec of membershipProof) {
+      _out.writeHash(membershipProof_vec);
+    }
+  });
+  const _secretInput = (secret_input_lambda: BN): CompactBitArray => AbiBitOutput.serialize((_out) => {
+    _out.writeSignedBigInteger(secret_input_lambda, 16);
+  });
+  return new SecretInputBuilder(_publicRpc, _secretInput);
+}
+
+export function castAllocation(voterIndex: number, membershipProof: Hash[]): SecretInputBuilder<BudgetAllocation> {
+  const _publicRpc: Buffer = AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("67", "hex"));
+    _out.writeU32(voterIndex);
+    _out.writeI32(membershipProof.length);
+    for (const membershipProof_vec of membershipProof) {
+      _out.writeHash(membershipProof_vec);
+    }
+  });
+  const _secretInput = (secret_input_lambda: BudgetAllocation): CompactBitArray => AbiBitOutput.serialize((_out) => {
+    serializeBudgetAllocation(_out, secret_input_lambda);
+  });
+  return new SecretInputBuilder(_publicRpc, _secretInput);
+}
+
 export function deserializeState(state: StateWithClient): BallotState;
 export function deserializeState(bytes: Buffer): BallotState;
 export function deserializeState(
@@ -756,7 +2288,21 @@ export function deserializeState(
 
 export type Action =
   | ComputeTallyAction
+  | RetryTallyAction
+  | AbandonTallyAction
+  | ResetVoteCheckAction
   | SyncVotersAction
+  | SetEventListenerAction
+  | PauseBallotAction
+  | ResumeBallotAction
+  | UpdateBallotMetadataAction
+  | CheckTokenEligibilityAction
+  | PruneProcessesAction
+  | RequestResultAction
+  | FoldVotesAction
+  | ConfirmCancellationAction
+  | ApproveActionAction
+  | PublishDrawnMemberAction
   | CancelBallotAction
   | HandleOrgEventAction
   | StatusChangedAction;
@@ -764,9 +2310,67 @@ export type Action =
 export interface ComputeTallyAction {
   discriminant: "compute_tally";
 }
+export interface RetryTallyAction {
+  discriminant: "retry_tally";
+}
+export interface AbandonTallyAction {
+  discriminant: "abandon_tally";
+  note: string;
+}
+export interface ResetVoteCheckAction {
+  discriminant: "reset_vote_check";
+  skip: boolean;
+}
 export interface SyncVotersAction {
   discriminant: "sync_voters";
-  newEligibleVoters: BlockchainAddress[];
+  newVoterRoot: Hash;
+  newVoterCount: number;
+}
+export interface SetEventListenerAction {
+  discriminant: "set_event_listener";
+  eventListener: Option<BlockchainAddress>;
+}
+export interface PauseBallotAction {
+  discriminant: "pause_ballot";
+}
+export interface ResumeBallotAction {
+  discriminant: "resume_ballot";
+  extendEndTime: boolean;
+}
+export interface UpdateBallotMetadataAction {
+  discriminant: "update_ballot_metadata";
+  title: Option<string>;
+  description: Option<string>;
+  options: Option<string[]>;
+  costs: Option<number[]>;
+}
+export interface CheckTokenEligibilityAction {
+  discriminant: "check_token_eligibility";
+}
+export interface PruneProcessesAction {
+  discriminant: "prune_processes";
+  maxAgeSeconds: BN;
+}
+export interface RequestResultAction {
+  discriminant: "request_result";
+  voterIndex: number;
+  membershipProof: Hash[];
+}
+export interface FoldVotesAction {
+  discriminant: "fold_votes";
+}
+export interface ConfirmCancellationAction {
+  discriminant: "confirm_cancellation";
+}
+export interface ApproveActionAction {
+  discriminant: "approve_action";
+  action: BallotAction;
+}
+export interface PublishDrawnMemberAction {
+  discriminant: "publish_drawn_member";
+  seat: number;
+  member: BlockchainAddress;
+  membershipProof: Hash[];
 }
 export interface CancelBallotAction {
   discriminant: "cancel_ballot";
@@ -786,8 +2390,36 @@ export function deserializeAction(bytes: Buffer): Action {
   const contract = new BallotGenerated(undefined, undefined);
   if (shortname === "01") {
     return contract.deserializeComputeTallyAction(input);
+  } else if (shortname === "02") {
+    return contract.deserializeRetryTallyAction(input);
+  } else if (shortname === "03") {
+    return contract.deserializeAbandonTallyAction(input);
+  } else if (shortname === "04") {
+    return contract.deserializeResetVoteCheckAction(input);
   } else if (shortname === "05") {
     return contract.deserializeSyncVotersAction(input);
+  } else if (shortname === "06") {
+    return contract.deserializeSetEventListenerAction(input);
+  } else if (shortname === "07") {
+    return contract.deserializePauseBallotAction(input);
+  } else if (shortname === "08") {
+    return contract.deserializeResumeBallotAction(input);
+  } else if (shortname === "09") {
+    return contract.deserializeUpdateBallotMetadataAction(input);
+  } else if (shortname === "0a") {
+    return contract.deserializeCheckTokenEligibilityAction(input);
+  } else if (shortname === "0b") {
+    return contract.deserializePruneProcessesAction(input);
+  } else if (shortname === "0c") {
+    return contract.deserializeRequestResultAction(input);
+  } else if (shortname === "0d") {
+    return contract.deserializeFoldVotesAction(input);
+  } else if (shortname === "0e") {
+    return contract.deserializeConfirmCancellationAction(input);
+  } else if (shortname === "0f") {
+    return contract.deserializeApproveActionAction(input);
+  } else if (shortname === "10") {
+    return contract.deserializePublishDrawnMemberAction(input);
   } else if (shortname === "11") {
     return contract.deserializeCancelBallotAction(input);
   } else if (shortname === "30") {
@@ -798,6 +2430,23 @@ export function deserializeAction(bytes: Buffer): Action {
   throw new Error("Illegal shortname: " + shortname);
 }
 
+export type Callback =
+  | TokenBalanceCallbackCallback;
+
+export interface TokenBalanceCallbackCallback {
+  discriminant: "token_balance_callback";
+  voter: BlockchainAddress;
+}
+export function deserializeCallback(bytes: Buffer): Callback {
+  const input = AbiByteInput.createBigEndian(bytes);
+  const shortname = input.readShortnameString();
+  const contract = new BallotGenerated(undefined, undefined);
+  if (shortname === "21") {
+    return contract.deserializeTokenBalanceCallbackCallback(input);
+  }
+  throw new Error("Illegal shortname: " + shortname);
+}
+
 export type Init =
   | InitializeInit;
 
@@ -808,8 +2457,19 @@ export interface InitializeInit {
   description: string;
   organization: BlockchainAddress;
   administrator: BlockchainAddress;
-  eligibleVoters: BlockchainAddress[];
+  voterRoot: Hash;
+  voterCount: number;
   durationSeconds: BN;
+  membershipPolicy: MembershipPolicy;
+  attachments: Attachment[];
+  eligibility: Eligibility;
+  resultDisclosure: ResultDisclosure;
+  participationMode: ParticipationMode;
+  ballotMode: BallotMode;
+  runoffRule: RunoffRule;
+  parentBallot: Option<BlockchainAddress>;
+  roles: BallotRoles;
+  approvalPolicy: ApprovalPolicy;
 }
 export function deserializeInit(bytes: Buffer): Init {
   const input = AbiByteInput.createBigEndian(bytes);
@@ -820,4 +2480,3 @@ export function deserializeInit(bytes: Buffer): Init {
   }
   throw new Error("Illegal shortname: " + shortname);
 }
-
@@ -36,6 +36,7 @@ export class OrganizationGenerated {
     this._client = client;
   }
   public deserializeOrganizationState(_input: AbiInput): OrganizationState {
+    const stateVersion: number = _input.readU32();
     const owner: BlockchainAddress = _input.readAddress();
     const administrators_setLength = _input.readI32();
     const administrators: BlockchainAddress[] = [];
@@ -53,6 +54,17 @@ export class OrganizationGenerated {
       const members_elem: BlockchainAddress = _input.readAddress();
       members.push(members_elem);
     }
+    const memberSince_mapLength = _input.readI32();
+    const memberSince: Map<BlockchainAddress, BN> = new Map();
+    for (
+      let memberSince_i = 0;
+      memberSince_i < memberSince_mapLength;
+      memberSince_i++
+    ) {
+      const memberSince_key: BlockchainAddress = _input.readAddress();
+      const memberSince_value: BN = _input.readU64();
+      memberSince.set(memberSince_key, memberSince_value);
+    }
     const name: string = _input.readString();
     const description: string = _input.readString();
     const profileImage: string = _input.readString();
@@ -75,23 +87,58 @@ export class OrganizationGenerated {
       ballots.push(ballots_elem);
     }
     const eventNonce: BN = _input.readU64();
-    const ballotProcesses_mapLength = _input.readI32();
-    const ballotProcesses: Map<string, BallotProcessState> = new Map();
+    const ballotProcesses: BallotProcessLog =
+      this.deserializeBallotProcessLog(_input);
+    const factoryAddress: BlockchainAddress = _input.readAddress();
+    const ballotStatuses_mapLength = _input.readI32();
+    const ballotStatuses: Map<BlockchainAddress, BallotStatus> = new Map();
     for (
-      let ballotProcesses_i = 0;
-      ballotProcesses_i < ballotProcesses_mapLength;
-      ballotProcesses_i++
+      let ballotStatuses_i = 0;
+      ballotStatuses_i < ballotStatuses_mapLength;
+      ballotStatuses_i++
     ) {
-      const ballotProcesses_key: string = _input.readString();
-      const ballotProcesses_value: BallotProcessState =
-        this.deserializeBallotProcessState(_input);
-      ballotProcesses.set(ballotProcesses_key, ballotProcesses_value);
+      const ballotStatuses_key: BlockchainAddress = _input.readAddress();
+      const ballotStatuses_value: BallotStatus =
+        this.deserializeBallotStatus(_input);
+      ballotStatuses.set(ballotStatuses_key, ballotStatuses_value);
+    }
+    const processCounter: ProcessCounter =
+      this.deserializeProcessCounter(_input);
+    let upgrader: Option<BlockchainAddress> = undefined;
+    const upgrader_isSome = _input.readBoolean();
+    if (upgrader_isSome) {
+      const upgrader_option: BlockchainAddress = _input.readAddress();
+      upgrader = upgrader_option;
+    }
+    const runoffTemplates_mapLength = _input.readI32();
+    const runoffTemplates: Map<BlockchainAddress, RunoffTemplate> = new Map();
+    for (
+      let runoffTemplates_i = 0;
+      runoffTemplates_i < runoffTemplates_mapLength;
+      runoffTemplates_i++
+    ) {
+      const runoffTemplates_key: BlockchainAddress = _input.readAddress();
+      const runoffTemplates_value: RunoffTemplate =
+        this.deserializeRunoffTemplate(_input);
+      runoffTemplates.set(runoffTemplates_key, runoffTemplates_value);
+    }
+    const runoffParents_mapLength = _input.readI32();
+    const runoffParents: Map<BlockchainAddress, BlockchainAddress> = new Map();
+    for (
+      let runoffParents_i = 0;
+      runoffParents_i < runoffParents_mapLength;
+      runoffParents_i++
+    ) {
+      const runoffParents_key: BlockchainAddress = _input.readAddress();
+      const runoffParents_value: BlockchainAddress = _input.readAddress();
+      runoffParents.set(runoffParents_key, runoffParents_value);
     }
-    const factoryAddress: BlockchainAddress = _input.readAddress();
     return {
+      stateVersion,
       owner,
       administrators,
       members,
+      memberSince,
       name,
       description,
       profileImage,
@@ -105,8 +152,38 @@ export class OrganizationGenerated {
       eventNonce,
       ballotProcesses,
       factoryAddress,
+      ballotStatuses,
+      processCounter,
+      upgrader,
+      runoffTemplates,
+      runoffParents,
     };
   }
+  public deserializeBallotProcessLog(_input: AbiInput): BallotProcessLog {
+    const records_mapLength = _input.readI32();
+    const records: Map<string, BallotProcessRecord> = new Map();
+    for (let records_i = 0; records_i < records_mapLength; records_i++) {
+      const records_key: string = _input.readString();
+      const records_value: BallotProcessRecord =
+        this.deserializeBallotProcessRecord(_input);
+      records.set(records_key, records_value);
+    }
+    const prunedCount: BN = _input.readU64();
+    const prunedFailures: BN = _input.readU64();
+    return { records, prunedCount, prunedFailures };
+  }
+  public deserializeBallotProcessRecord(_input: AbiInput): BallotProcessRecord {
+    const state: BallotProcessState =
+      this.deserializeBallotProcessState(_input);
+    const timestamp: BN = _input.readU64();
+    let failureReason: Option<string> = undefined;
+    const failureReason_isSome = _input.readBoolean();
+    if (failureReason_isSome) {
+      const failureReason_option: string = _input.readString();
+      failureReason = failureReason_option;
+    }
+    return { state, timestamp, failureReason };
+  }
   public deserializeBallotProcessState(_input: AbiInput): BallotProcessState {
     const discriminant = _input.readU8();
     if (discriminant === 0) {
@@ -154,6 +231,273 @@ export class OrganizationGenerated {
   ): BallotProcessStateCancelled {
     return { discriminant: BallotProcessStateD.Cancelled };
   }
+  public deserializeBallotStatus(_input: AbiInput): BallotStatus {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeBallotStatusActive(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeBallotStatusTallying(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeBallotStatusCompleted(_input);
+    } else if (discriminant === 3) {
+      return this.deserializeBallotStatusCancelled(_input);
+    } else if (discriminant === 4) {
+      return this.deserializeBallotStatusTallyFailed(_input);
+    } else if (discriminant === 5) {
+      return this.deserializeBallotStatusPaused(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeBallotStatusActive(_input: AbiInput): BallotStatusActive {
+    return { discriminant: BallotStatusD.Active };
+  }
+  public deserializeBallotStatusTallying(
+    _input: AbiInput
+  ): BallotStatusTallying {
+    return { discriminant: BallotStatusD.Tallying };
+  }
+  public deserializeBallotStatusCompleted(
+    _input: AbiInput
+  ): BallotStatusCompleted {
+    return { discriminant: BallotStatusD.Completed };
+  }
+  public deserializeBallotStatusCancelled(
+    _input: AbiInput
+  ): BallotStatusCancelled {
+    return { discriminant: BallotStatusD.Cancelled };
+  }
+  public deserializeBallotStatusTallyFailed(
+    _input: AbiInput
+  ): BallotStatusTallyFailed {
+    return { discriminant: BallotStatusD.TallyFailed };
+  }
+  public deserializeBallotStatusPaused(_input: AbiInput): BallotStatusPaused {
+    return { discriminant: BallotStatusD.Paused };
+  }
+  public deserializeProcessCounter(_input: AbiInput): ProcessCounter {
+    const next: BN = _input.readU64();
+    return { next };
+  }
+  public deserializeRunoffTemplate(_input: AbiInput): RunoffTemplate {
+    const title: string = _input.readString();
+    const description: string = _input.readString();
+    const administrator: BlockchainAddress = _input.readAddress();
+    const durationSeconds: BN = _input.readU64();
+    const attachments_vecLength = _input.readI32();
+    const attachments: Attachment[] = [];
+    for (
+      let attachments_i = 0;
+      attachments_i < attachments_vecLength;
+      attachments_i++
+    ) {
+      const attachments_elem: Attachment = this.deserializeAttachment(_input);
+      attachments.push(attachments_elem);
+    }
+    const eligibility: Eligibility = this.deserializeEligibility(_input);
+    const participationMode: ParticipationMode =
+      this.deserializeParticipationMode(_input);
+    const roles: BallotRoles = this.deserializeBallotRoles(_input);
+    const approvals: ApprovalSettings =
+      this.deserializeApprovalSettings(_input);
+    return {
+      title,
+      description,
+      administrator,
+      durationSeconds,
+      attachments,
+      eligibility,
+      participationMode,
+      roles,
+      approvals,
+    };
+  }
+  public deserializeAttachment(_input: AbiInput): Attachment {
+    const uri: string = _input.readString();
+    const sha256: string = _input.readString();
+    const mimeType: string = _input.readString();
+    return { uri, sha256, mimeType };
+  }
+  public deserializeEligibility(_input: AbiInput): Eligibility {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeEligibilityMembership(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeEligibilityTokenBalance(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeEligibilityMembership(
+    _input: AbiInput
+  ): EligibilityMembership {
+    return { discriminant: EligibilityD.Membership };
+  }
+  public deserializeEligibilityTokenBalance(
+    _input: AbiInput
+  ): EligibilityTokenBalance {
+    const token: BlockchainAddress = _input.readAddress();
+    const minBalance: BN = _input.readUnsignedBigInteger(16);
+    return { discriminant: EligibilityD.TokenBalance, token, minBalance };
+  }
+  public deserializeParticipationMode(_input: AbiInput): ParticipationMode {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeParticipationModePublic(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeParticipationModeAnonymous(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeParticipationModePublic(
+    _input: AbiInput
+  ): ParticipationModePublic {
+    return { discriminant: ParticipationModeD.Public };
+  }
+  public deserializeParticipationModeAnonymous(
+    _input: AbiInput
+  ): ParticipationModeAnonymous {
+    const issuanceSeconds: BN = _input.readU64();
+    return { discriminant: ParticipationModeD.Anonymous, issuanceSeconds };
+  }
+  public deserializeBallotRoles(_input: AbiInput): BallotRoles {
+    const talliers_vecLength = _input.readI32();
+    const talliers: BlockchainAddress[] = [];
+    for (let talliers_i = 0; talliers_i < talliers_vecLength; talliers_i++) {
+      const talliers_elem: BlockchainAddress = _input.readAddress();
+      talliers.push(talliers_elem);
+    }
+    const cancellers_vecLength = _input.readI32();
+    const cancellers: BlockchainAddress[] = [];
+    for (
+      let cancellers_i = 0;
+      cancellers_i < cancellers_vecLength;
+      cancellers_i++
+    ) {
+      const cancellers_elem: BlockchainAddress = _input.readAddress();
+      cancellers.push(cancellers_elem);
+    }
+    const observers_vecLength = _input.readI32();
+    const observers: BlockchainAddress[] = [];
+    for (
+      let observers_i = 0;
+      observers_i < observers_vecLength;
+      observers_i++
+    ) {
+      const observers_elem: BlockchainAddress = _input.readAddress();
+      observers.push(observers_elem);
+    }
+    return { talliers, cancellers, observers };
+  }
+  public deserializeApprovalSettings(_input: AbiInput): ApprovalSettings {
+    const threshold: number = _input.readU32();
+    const windowSeconds: BN = _input.readU64();
+    return { threshold, windowSeconds };
+  }
+  public deserializeMembershipPolicy(_input: AbiInput): MembershipPolicy {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeMembershipPolicySnapshot(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeMembershipPolicyAddOnly(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeMembershipPolicyLiveSync(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeMembershipPolicySnapshot(
+    _input: AbiInput
+  ): MembershipPolicySnapshot {
+    return { discriminant: MembershipPolicyD.Snapshot };
+  }
+  public deserializeMembershipPolicyAddOnly(
+    _input: AbiInput
+  ): MembershipPolicyAddOnly {
+    return { discriminant: MembershipPolicyD.AddOnly };
+  }
+  public deserializeMembershipPolicyLiveSync(
+    _input: AbiInput
+  ): MembershipPolicyLiveSync {
+    return { discriminant: MembershipPolicyD.LiveSync };
+  }
+  public deserializeResultDisclosure(_input: AbiInput): ResultDisclosure {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeResultDisclosurePublic(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeResultDisclosureVoters(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeResultDisclosureAdministrators(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeResultDisclosurePublic(
+    _input: AbiInput
+  ): ResultDisclosurePublic {
+    return { discriminant: ResultDisclosureD.Public };
+  }
+  public deserializeResultDisclosureVoters(
+    _input: AbiInput
+  ): ResultDisclosureVoters {
+    return { discriminant: ResultDisclosureD.Voters };
+  }
+  public deserializeResultDisclosureAdministrators(
+    _input: AbiInput
+  ): ResultDisclosureAdministrators {
+    const administrators_vecLength = _input.readI32();
+    const administrators: BlockchainAddress[] = [];
+    for (
+      let administrators_i = 0;
+      administrators_i < administrators_vecLength;
+      administrators_i++
+    ) {
+      const administrators_elem: BlockchainAddress = _input.readAddress();
+      administrators.push(administrators_elem);
+    }
+    return { discriminant: ResultDisclosureD.Administrators, administrators };
+  }
+  public deserializeBallotMode(_input: AbiInput): BallotMode {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeBallotModeVote(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeBallotModeSortition(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeBallotModeBudgeting(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeBallotModeVote(_input: AbiInput): BallotModeVote {
+    return { discriminant: BallotModeD.Vote };
+  }
+  public deserializeBallotModeSortition(_input: AbiInput): BallotModeSortition {
+    const seats: number = _input.readU32();
+    return { discriminant: BallotModeD.Sortition, seats };
+  }
+  public deserializeBallotModeBudgeting(_input: AbiInput): BallotModeBudgeting {
+    const budget: number = _input.readU32();
+    const costs_vecLength = _input.readI32();
+    const costs: number[] = [];
+    for (let costs_i = 0; costs_i < costs_vecLength; costs_i++) {
+      const costs_elem: number = _input.readU32();
+      costs.push(costs_elem);
+    }
+    return { discriminant: BallotModeD.Budgeting, budget, costs };
+  }
+  public deserializeRunoffRule(_input: AbiInput): RunoffRule {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeRunoffRuleNone(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeRunoffRuleTopTwo(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeRunoffRuleNone(_input: AbiInput): RunoffRuleNone {
+    return { discriminant: RunoffRuleD.None };
+  }
+  public deserializeRunoffRuleTopTwo(_input: AbiInput): RunoffRuleTopTwo {
+    const durationSeconds: BN = _input.readU64();
+    return { discriminant: RunoffRuleD.TopTwo, durationSeconds };
+  }
   public deserializeOrganizationEvent(_input: AbiInput): OrganizationEvent {
     const discriminant = _input.readU8();
     if (discriminant === 0) {
@@ -243,6 +587,160 @@ export class OrganizationGenerated {
       processId,
     };
   }
+  public deserializeBallotEvent(_input: AbiInput): BallotEvent {
+    const discriminant = _input.readU8();
+    if (discriminant === 0) {
+      return this.deserializeBallotEventMembersUpdated(_input);
+    } else if (discriminant === 1) {
+      return this.deserializeBallotEventVoteCast(_input);
+    } else if (discriminant === 2) {
+      return this.deserializeBallotEventTallyStarted(_input);
+    } else if (discriminant === 3) {
+      return this.deserializeBallotEventTallyCompleted(_input);
+    } else if (discriminant === 4) {
+      return this.deserializeBallotEventStatusChanged(_input);
+    } else if (discriminant === 5) {
+      return this.deserializeBallotEventCommitteeDrawn(_input);
+    } else if (discriminant === 6) {
+      return this.deserializeBallotEventRunoffRequired(_input);
+    } else if (discriminant === 7) {
+      return this.deserializeBallotEventBudgetAllocated(_input);
+    } else if (discriminant === 8) {
+      return this.deserializeBallotEventVoteRejected(_input);
+    }
+    throw new Error("Unknown discriminant: " + discriminant);
+  }
+  public deserializeBallotEventMembersUpdated(
+    _input: AbiInput
+  ): BallotEventMembersUpdated {
+    const added_vecLength = _input.readI32();
+    const added: BlockchainAddress[] = [];
+    for (let added_i = 0; added_i < added_vecLength; added_i++) {
+      const added_elem: BlockchainAddress = _input.readAddress();
+      added.push(added_elem);
+    }
+    const removed_vecLength = _input.readI32();
+    const removed: BlockchainAddress[] = [];
+    for (let removed_i = 0; removed_i < removed_vecLength; removed_i++) {
+      const removed_elem: BlockchainAddress = _input.readAddress();
+      removed.push(removed_elem);
+    }
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return {
+      discriminant: BallotEventD.MembersUpdated,
+      added,
+      removed,
+      timestamp,
+      processId,
+    };
+  }
+  public deserializeBallotEventVoteCast(_input: AbiInput): BallotEventVoteCast {
+    const voter: BlockchainAddress = _input.readAddress();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.VoteCast, voter, timestamp, processId };
+  }
+  public deserializeBallotEventTallyStarted(
+    _input: AbiInput
+  ): BallotEventTallyStarted {
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.TallyStarted, timestamp, processId };
+  }
+  public deserializeBallotEventTallyCompleted(
+    _input: AbiInput
+  ): BallotEventTallyCompleted {
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return { discriminant: BallotEventD.TallyCompleted, timestamp, processId };
+  }
+  public deserializeBallotEventStatusChanged(
+    _input: AbiInput
+  ): BallotEventStatusChanged {
+    const status: BallotStatus = this.deserializeBallotStatus(_input);
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return {
+      discriminant: BallotEventD.StatusChanged,
+      status,
+      timestamp,
+      processId,
+    };
+  }
+  public deserializeBallotEventCommitteeDrawn(
+    _input: AbiInput
+  ): BallotEventCommitteeDrawn {
+    const selected_vecLength = _input.readI32();
+    const selected: BlockchainAddress[] = [];
+    for (let selected_i = 0; selected_i < selected_vecLength; selected_i++) {
+      const selected_elem: BlockchainAddress = _input.readAddress();
+      selected.push(selected_elem);
+    }
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return {
+      discriminant: BallotEventD.CommitteeDrawn,
+      selected,
+      timestamp,
+      processId,
+    };
+  }
+  public deserializeBallotEventRunoffRequired(
+    _input: AbiInput
+  ): BallotEventRunoffRequired {
+    const options_vecLength = _input.readI32();
+    const options: string[] = [];
+    for (let options_i = 0; options_i < options_vecLength; options_i++) {
+      const options_elem: string = _input.readString();
+      options.push(options_elem);
+    }
+    const voterRoot: Hash = _input.readHash();
+    const voterCount: number = _input.readU32();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return {
+      discriminant: BallotEventD.RunoffRequired,
+      options,
+      voterRoot,
+      voterCount,
+      timestamp,
+      processId,
+    };
+  }
+  public deserializeBallotEventBudgetAllocated(
+    _input: AbiInput
+  ): BallotEventBudgetAllocated {
+    const funded_vecLength = _input.readI32();
+    const funded: string[] = [];
+    for (let funded_i = 0; funded_i < funded_vecLength; funded_i++) {
+      const funded_elem: string = _input.readString();
+      funded.push(funded_elem);
+    }
+    const spent: number = _input.readU32();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return {
+      discriminant: BallotEventD.BudgetAllocated,
+      funded,
+      spent,
+      timestamp,
+      processId,
+    };
+  }
+  public deserializeBallotEventVoteRejected(
+    _input: AbiInput
+  ): BallotEventVoteRejected {
+    const voter: BlockchainAddress = _input.readAddress();
+    const timestamp: BN = _input.readU64();
+    const processId: string = _input.readString();
+    return {
+      discriminant: BallotEventD.VoteRejected,
+      voter,
+      timestamp,
+      processId,
+    };
+  }
   public async getState(): Promise<OrganizationState> {
     const bytes = await this._client?.getContractStateBinary(this._address!);
     if (bytes === undefined) {
@@ -306,6 +804,29 @@ export class OrganizationGenerated {
     const description: string = _input.readString();
     const administrator: BlockchainAddress = _input.readAddress();
     const durationSeconds: BN = _input.readU64();
+    const membershipPolicy: MembershipPolicy =
+      this.deserializeMembershipPolicy(_input);
+    const attachments_vecLength = _input.readI32();
+    const attachments: Attachment[] = [];
+    for (
+      let attachments_i = 0;
+      attachments_i < attachments_vecLength;
+      attachments_i++
+    ) {
+      const attachments_elem: Attachment = this.deserializeAttachment(_input);
+      attachments.push(attachments_elem);
+    }
+    const eligibility: Eligibility = this.deserializeEligibility(_input);
+    const resultDisclosure: ResultDisclosure =
+      this.deserializeResultDisclosure(_input);
+    const participationMode: ParticipationMode =
+      this.deserializeParticipationMode(_input);
+    const ballotMode: BallotMode = this.deserializeBallotMode(_input);
+    const runoffRule: RunoffRule = this.deserializeRunoffRule(_input);
+    const roles: BallotRoles = this.deserializeBallotRoles(_input);
+    const approvals: ApprovalSettings =
+      this.deserializeApprovalSettings(_input);
+    const minTenureDays: number = _input.readU32();
     return {
       discriminant: "deploy_ballot",
       options,
@@ -313,6 +834,16 @@ export class OrganizationGenerated {
       description,
       administrator,
       durationSeconds,
+      membershipPolicy,
+      attachments,
+      eligibility,
+      resultDisclosure,
+      participationMode,
+      ballotMode,
+      runoffRule,
+      roles,
+      approvals,
+      minTenureDays,
     };
   }
 
@@ -373,6 +904,30 @@ export class OrganizationGenerated {
     };
   }
 
+  public deserializePruneProcessesAction(
+    _input: AbiInput
+  ): PruneProcessesAction {
+    const maxAgeSeconds: BN = _input.readU64();
+    return { discriminant: "prune_processes", maxAgeSeconds };
+  }
+
+  public deserializeSetUpgraderAction(_input: AbiInput): SetUpgraderAction {
+    let upgrader: Option<BlockchainAddress> = undefined;
+    const upgrader_isSome = _input.readBoolean();
+    if (upgrader_isSome) {
+      const upgrader_option: BlockchainAddress = _input.readAddress();
+      upgrader = upgrader_option;
+    }
+    return { discriminant: "set_upgrader", upgrader };
+  }
+
+  public deserializeSyncBallotVotersAction(
+    _input: AbiInput
+  ): SyncBallotVotersAction {
+    const ballot: BlockchainAddress = _input.readAddress();
+    return { discriminant: "sync_ballot_voters", ballot };
+  }
+
   public deserializeHandleBallotDeployedEventAction(
     _input: AbiInput
   ): HandleBallotDeployedEventAction {
@@ -380,6 +935,13 @@ export class OrganizationGenerated {
     return { discriminant: "handle_ballot_deployed_event", event };
   }
 
+  public deserializeHandleBallotEventAction(
+    _input: AbiInput
+  ): HandleBallotEventAction {
+    const event: BallotEvent = this.deserializeBallotEvent(_input);
+    return { discriminant: "handle_ballot_event", event };
+  }
+
   public deserializeDeployBallotCallbackCallback(
     _input: AbiInput
   ): DeployBallotCallbackCallback {
@@ -394,6 +956,21 @@ export class OrganizationGenerated {
     };
   }
 
+  public deserializeTokenProbeCallbackCallback(
+    _input: AbiInput
+  ): TokenProbeCallbackCallback {
+    const ballotContractAddress: BlockchainAddress = _input.readAddress();
+    const ballotTitle: string = _input.readString();
+    const initData_vecLength = _input.readI32();
+    const initData: Buffer = _input.readBytes(initData_vecLength);
+    return {
+      discriminant: "token_probe_callback",
+      ballotContractAddress,
+      ballotTitle,
+      initData,
+    };
+  }
+
   public deserializeInitializeInit(_input: AbiInput): InitializeInit {
     const name: string = _input.readString();
     const description: string = _input.readString();
@@ -429,9 +1006,11 @@ export class OrganizationGenerated {
   }
 }
 export interface OrganizationState {
+  stateVersion: number;
   owner: BlockchainAddress;
   administrators: BlockchainAddress[];
   members: BlockchainAddress[];
+  memberSince: Map<BlockchainAddress, BN>;
   name: string;
   description: string;
   profileImage: string;
@@ -443,8 +1022,25 @@ export interface OrganizationState {
   ballotContractAbi: Buffer;
   ballots: BlockchainAddress[];
   eventNonce: BN;
-  ballotProcesses: Map<string, BallotProcessState>;
+  ballotProcesses: BallotProcessLog;
   factoryAddress: BlockchainAddress;
+  ballotStatuses: Map<BlockchainAddress, BallotStatus>;
+  processCounter: ProcessCounter;
+  upgrader: Option<BlockchainAddress>;
+  runoffTemplates: Map<BlockchainAddress, RunoffTemplate>;
+  runoffParents: Map<BlockchainAddress, BlockchainAddress>;
+}
+
+export interface BallotProcessLog {
+  records: Map<string, BallotProcessRecord>;
+  prunedCount: BN;
+  prunedFailures: BN;
+}
+
+export interface BallotProcessRecord {
+  state: BallotProcessState;
+  timestamp: BN;
+  failureReason: Option<string>;
 }
 
 export enum BallotProcessStateD {
@@ -487,17 +1083,470 @@ export interface BallotProcessStateCancelled {
   discriminant: BallotProcessStateD.Cancelled;
 }
 
-export enum OrganizationEventD {
-  BallotDeployed = 0,
-  MembersAdded = 1,
-  MembersRemoved = 2,
-  BallotDeployFailed = 3,
+export enum BallotStatusD {
+  Active = 0,
+  Tallying = 1,
+  Completed = 2,
+  Cancelled = 3,
+  TallyFailed = 4,
+  Paused = 5,
 }
-export type OrganizationEvent =
-  | OrganizationEventBallotDeployed
-  | OrganizationEventMembersAdded
-  | OrganizationEventMembersRemoved
-  | OrganizationEventBallotDeployFailed;
+export type BallotStatus =
+  | BallotStatusActive
+  | BallotStatusTallying
+  | BallotStatusCompleted
+  | BallotStatusCancelled
+  | BallotStatusTallyFailed
+  | BallotStatusPaused;
+function serializeBallotStatus(out: AbiOutput, value: BallotStatus): void {
+  if (value.discriminant === BallotStatusD.Active) {
+    return serializeBallotStatusActive(out, value);
+  } else if (value.discriminant === BallotStatusD.Tallying) {
+    return serializeBallotStatusTallying(out, value);
+  } else if (value.discriminant === BallotStatusD.Completed) {
+    return serializeBallotStatusCompleted(out, value);
+  } else if (value.discriminant === BallotStatusD.Cancelled) {
+    return serializeBallotStatusCancelled(out, value);
+  } else if (value.discriminant === BallotStatusD.TallyFailed) {
+    return serializeBallotStatusTallyFailed(out, value);
+  } else if (value.discriminant === BallotStatusD.Paused) {
+    return serializeBallotStatusPaused(out, value);
+  }
+}
+
+export interface BallotStatusActive {
+  discriminant: BallotStatusD.Active;
+}
+function serializeBallotStatusActive(
+  _out: AbiOutput,
+  _value: BallotStatusActive
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusTallying {
+  discriminant: BallotStatusD.Tallying;
+}
+function serializeBallotStatusTallying(
+  _out: AbiOutput,
+  _value: BallotStatusTallying
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusCompleted {
+  discriminant: BallotStatusD.Completed;
+}
+function serializeBallotStatusCompleted(
+  _out: AbiOutput,
+  _value: BallotStatusCompleted
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusCancelled {
+  discriminant: BallotStatusD.Cancelled;
+}
+function serializeBallotStatusCancelled(
+  _out: AbiOutput,
+  _value: BallotStatusCancelled
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusTallyFailed {
+  discriminant: BallotStatusD.TallyFailed;
+}
+function serializeBallotStatusTallyFailed(
+  _out: AbiOutput,
+  _value: BallotStatusTallyFailed
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotStatusPaused {
+  discriminant: BallotStatusD.Paused;
+}
+function serializeBallotStatusPaused(
+  _out: AbiOutput,
+  _value: BallotStatusPaused
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ProcessCounter {
+  next: BN;
+}
+
+export interface RunoffTemplate {
+  title: string;
+  description: string;
+  administrator: BlockchainAddress;
+  durationSeconds: BN;
+  attachments: Attachment[];
+  eligibility: Eligibility;
+  participationMode: ParticipationMode;
+  roles: BallotRoles;
+  approvals: ApprovalSettings;
+}
+
+export interface Attachment {
+  uri: string;
+  sha256: string;
+  mimeType: string;
+}
+function serializeAttachment(_out: AbiOutput, _value: Attachment): void {
+  const { uri, sha256, mimeType } = _value;
+  _out.writeString(uri);
+  _out.writeString(sha256);
+  _out.writeString(mimeType);
+}
+
+export enum EligibilityD {
+  Membership = 0,
+  TokenBalance = 1,
+}
+export type Eligibility = EligibilityMembership | EligibilityTokenBalance;
+function serializeEligibility(out: AbiOutput, value: Eligibility): void {
+  if (value.discriminant === EligibilityD.Membership) {
+    return serializeEligibilityMembership(out, value);
+  } else if (value.discriminant === EligibilityD.TokenBalance) {
+    return serializeEligibilityTokenBalance(out, value);
+  }
+}
+
+export interface EligibilityMembership {
+  discriminant: EligibilityD.Membership;
+}
+function serializeEligibilityMembership(
+  _out: AbiOutput,
+  _value: EligibilityMembership
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface EligibilityTokenBalance {
+  discriminant: EligibilityD.TokenBalance;
+  token: BlockchainAddress;
+  minBalance: BN;
+}
+function serializeEligibilityTokenBalance(
+  _out: AbiOutput,
+  _value: EligibilityTokenBalance
+): void {
+  const { token, minBalance } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeAddress(token);
+  _out.writeUnsignedBigInteger(minBalance, 16);
+}
+
+export enum ParticipationModeD {
+  Public = 0,
+  Anonymous = 1,
+}
+export type ParticipationMode =
+  | ParticipationModePublic
+  | ParticipationModeAnonymous;
+function serializeParticipationMode(
+  out: AbiOutput,
+  value: ParticipationMode
+): void {
+  if (value.discriminant === ParticipationModeD.Public) {
+    return serializeParticipationModePublic(out, value);
+  } else if (value.discriminant === ParticipationModeD.Anonymous) {
+    return serializeParticipationModeAnonymous(out, value);
+  }
+}
+
+export interface ParticipationModePublic {
+  discriminant: ParticipationModeD.Public;
+}
+function serializeParticipationModePublic(
+  _out: AbiOutput,
+  _value: ParticipationModePublic
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ParticipationModeAnonymous {
+  discriminant: ParticipationModeD.Anonymous;
+  issuanceSeconds: BN;
+}
+function serializeParticipationModeAnonymous(
+  _out: AbiOutput,
+  _value: ParticipationModeAnonymous
+): void {
+  const { issuanceSeconds } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU64(issuanceSeconds);
+}
+
+export interface BallotRoles {
+  talliers: BlockchainAddress[];
+  cancellers: BlockchainAddress[];
+  observers: BlockchainAddress[];
+}
+function serializeBallotRoles(_out: AbiOutput, _value: BallotRoles): void {
+  const { talliers, cancellers, observers } = _value;
+  _out.writeI32(talliers.length);
+  for (const talliers_vec of talliers) {
+    _out.writeAddress(talliers_vec);
+  }
+  _out.writeI32(cancellers.length);
+  for (const cancellers_vec of cancellers) {
+    _out.writeAddress(cancellers_vec);
+  }
+  _out.writeI32(observers.length);
+  for (const observers_vec of observers) {
+    _out.writeAddress(observers_vec);
+  }
+}
+
+export interface ApprovalSettings {
+  threshold: number;
+  windowSeconds: BN;
+}
+function serializeApprovalSettings(
+  _out: AbiOutput,
+  _value: ApprovalSettings
+): void {
+  const { threshold, windowSeconds } = _value;
+  _out.writeU32(threshold);
+  _out.writeU64(windowSeconds);
+}
+
+export enum MembershipPolicyD {
+  Snapshot = 0,
+  AddOnly = 1,
+  LiveSync = 2,
+}
+export type MembershipPolicy =
+  | MembershipPolicySnapshot
+  | MembershipPolicyAddOnly
+  | MembershipPolicyLiveSync;
+function serializeMembershipPolicy(
+  out: AbiOutput,
+  value: MembershipPolicy
+): void {
+  if (value.discriminant === MembershipPolicyD.Snapshot) {
+    return serializeMembershipPolicySnapshot(out, value);
+  } else if (value.discriminant === MembershipPolicyD.AddOnly) {
+    return serializeMembershipPolicyAddOnly(out, value);
+  } else if (value.discriminant === MembershipPolicyD.LiveSync) {
+    return serializeMembershipPolicyLiveSync(out, value);
+  }
+}
+
+export interface MembershipPolicySnapshot {
+  discriminant: MembershipPolicyD.Snapshot;
+}
+function serializeMembershipPolicySnapshot(
+  _out: AbiOutput,
+  _value: MembershipPolicySnapshot
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface MembershipPolicyAddOnly {
+  discriminant: MembershipPolicyD.AddOnly;
+}
+function serializeMembershipPolicyAddOnly(
+  _out: AbiOutput,
+  _value: MembershipPolicyAddOnly
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface MembershipPolicyLiveSync {
+  discriminant: MembershipPolicyD.LiveSync;
+}
+function serializeMembershipPolicyLiveSync(
+  _out: AbiOutput,
+  _value: MembershipPolicyLiveSync
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export enum ResultDisclosureD {
+  Public = 0,
+  Voters = 1,
+  Administrators = 2,
+}
+export type ResultDisclosure =
+  | ResultDisclosurePublic
+  | ResultDisclosureVoters
+  | ResultDisclosureAdministrators;
+function serializeResultDisclosure(
+  out: AbiOutput,
+  value: ResultDisclosure
+): void {
+  if (value.discriminant === ResultDisclosureD.Public) {
+    return serializeResultDisclosurePublic(out, value);
+  } else if (value.discriminant === ResultDisclosureD.Voters) {
+    return serializeResultDisclosureVoters(out, value);
+  } else if (value.discriminant === ResultDisclosureD.Administrators) {
+    return serializeResultDisclosureAdministrators(out, value);
+  }
+}
+
+export interface ResultDisclosurePublic {
+  discriminant: ResultDisclosureD.Public;
+}
+function serializeResultDisclosurePublic(
+  _out: AbiOutput,
+  _value: ResultDisclosurePublic
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ResultDisclosureVoters {
+  discriminant: ResultDisclosureD.Voters;
+}
+function serializeResultDisclosureVoters(
+  _out: AbiOutput,
+  _value: ResultDisclosureVoters
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface ResultDisclosureAdministrators {
+  discriminant: ResultDisclosureD.Administrators;
+  administrators: BlockchainAddress[];
+}
+function serializeResultDisclosureAdministrators(
+  _out: AbiOutput,
+  _value: ResultDisclosureAdministrators
+): void {
+  const { administrators } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(administrators.length);
+  for (const administrators_vec of administrators) {
+    _out.writeAddress(administrators_vec);
+  }
+}
+
+export enum BallotModeD {
+  Vote = 0,
+  Sortition = 1,
+  Budgeting = 2,
+}
+export type BallotMode =
+  | BallotModeVote
+  | BallotModeSortition
+  | BallotModeBudgeting;
+function serializeBallotMode(out: AbiOutput, value: BallotMode): void {
+  if (value.discriminant === BallotModeD.Vote) {
+    return serializeBallotModeVote(out, value);
+  } else if (value.discriminant === BallotModeD.Sortition) {
+    return serializeBallotModeSortition(out, value);
+  } else if (value.discriminant === BallotModeD.Budgeting) {
+    return serializeBallotModeBudgeting(out, value);
+  }
+}
+
+export interface BallotModeVote {
+  discriminant: BallotModeD.Vote;
+}
+function serializeBallotModeVote(
+  _out: AbiOutput,
+  _value: BallotModeVote
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface BallotModeSortition {
+  discriminant: BallotModeD.Sortition;
+  seats: number;
+}
+function serializeBallotModeSortition(
+  _out: AbiOutput,
+  _value: BallotModeSortition
+): void {
+  const { seats } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU32(seats);
+}
+
+export interface BallotModeBudgeting {
+  discriminant: BallotModeD.Budgeting;
+  budget: number;
+  costs: number[];
+}
+function serializeBallotModeBudgeting(
+  _out: AbiOutput,
+  _value: BallotModeBudgeting
+): void {
+  const { budget, costs } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU32(budget);
+  _out.writeI32(costs.length);
+  for (const costs_vec of costs) {
+    _out.writeU32(costs_vec);
+  }
+}
+
+export enum RunoffRuleD {
+  None = 0,
+  TopTwo = 1,
+}
+export type RunoffRule = RunoffRuleNone | RunoffRuleTopTwo;
+function serializeRunoffRule(out: AbiOutput, value: RunoffRule): void {
+  if (value.discriminant === RunoffRuleD.None) {
+    return serializeRunoffRuleNone(out, value);
+  } else if (value.discriminant === RunoffRuleD.TopTwo) {
+    return serializeRunoffRuleTopTwo(out, value);
+  }
+}
+
+export interface RunoffRuleNone {
+  discriminant: RunoffRuleD.None;
+}
+function serializeRunoffRuleNone(
+  _out: AbiOutput,
+  _value: RunoffRuleNone
+): void {
+  const {} = _value;
+  _out.writeU8(_value.discriminant);
+}
+
+export interface RunoffRuleTopTwo {
+  discriminant: RunoffRuleD.TopTwo;
+  durationSeconds: BN;
+}
+function serializeRunoffRuleTopTwo(
+  _out: AbiOutput,
+  _value: RunoffRuleTopTwo
+): void {
+  const { durationSeconds } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU64(durationSeconds);
+}
+
+export enum OrganizationEventD {
+  BallotDeployed = 0,
+  MembersAdded = 1,
+  MembersRemoved = 2,
+  BallotDeployFailed = 3,
+}
+export type OrganizationEvent =
+  | OrganizationEventBallotDeployed
+  | OrganizationEventMembersAdded
+  | OrganizationEventMembersRemoved
+  | OrganizationEventBallotDeployFailed;
 function serializeOrganizationEvent(
   out: AbiOutput,
   value: OrganizationEvent
@@ -601,6 +1650,221 @@ function serializeOrganizationEventBallotDeployFailed(
   _out.writeString(processId);
 }
 
+export enum BallotEventD {
+  MembersUpdated = 0,
+  VoteCast = 1,
+  TallyStarted = 2,
+  TallyCompleted = 3,
+  StatusChanged = 4,
+  CommitteeDrawn = 5,
+  RunoffRequired = 6,
+  BudgetAllocated = 7,
+  VoteRejected = 8,
+}
+export type BallotEvent =
+  | BallotEventMembersUpdated
+  | BallotEventVoteCast
+  | BallotEventTallyStarted
+  | BallotEventTallyCompleted
+  | BallotEventStatusChanged
+  | BallotEventCommitteeDrawn
+  | BallotEventRunoffRequired
+  | BallotEventBudgetAllocated
+  | BallotEventVoteRejected;
+function serializeBallotEvent(out: AbiOutput, value: BallotEvent): void {
+  if (value.discriminant === BallotEventD.MembersUpdated) {
+    return serializeBallotEventMembersUpdated(out, value);
+  } else if (value.discriminant === BallotEventD.VoteCast) {
+    return serializeBallotEventVoteCast(out, value);
+  } else if (value.discriminant === BallotEventD.TallyStarted) {
+    return serializeBallotEventTallyStarted(out, value);
+  } else if (value.discriminant === BallotEventD.TallyCompleted) {
+    return serializeBallotEventTallyCompleted(out, value);
+  } else if (value.discriminant === BallotEventD.StatusChanged) {
+    return serializeBallotEventStatusChanged(out, value);
+  } else if (value.discriminant === BallotEventD.CommitteeDrawn) {
+    return serializeBallotEventCommitteeDrawn(out, value);
+  } else if (value.discriminant === BallotEventD.RunoffRequired) {
+    return serializeBallotEventRunoffRequired(out, value);
+  } else if (value.discriminant === BallotEventD.BudgetAllocated) {
+    return serializeBallotEventBudgetAllocated(out, value);
+  } else if (value.discriminant === BallotEventD.VoteRejected) {
+    return serializeBallotEventVoteRejected(out, value);
+  }
+}
+
+export interface BallotEventMembersUpdated {
+  discriminant: BallotEventD.MembersUpdated;
+  added: BlockchainAddress[];
+  removed: BlockchainAddress[];
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventMembersUpdated(
+  _out: AbiOutput,
+  _value: BallotEventMembersUpdated
+): void {
+  const { added, removed, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(added.length);
+  for (const added_vec of added) {
+    _out.writeAddress(added_vec);
+  }
+  _out.writeI32(removed.length);
+  for (const removed_vec of removed) {
+    _out.writeAddress(removed_vec);
+  }
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventVoteCast {
+  discriminant: BallotEventD.VoteCast;
+  voter: BlockchainAddress;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventVoteCast(
+  _out: AbiOutput,
+  _value: BallotEventVoteCast
+): void {
+  const { voter, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeAddress(voter);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventTallyStarted {
+  discriminant: BallotEventD.TallyStarted;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventTallyStarted(
+  _out: AbiOutput,
+  _value: BallotEventTallyStarted
+): void {
+  const { timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventTallyCompleted {
+  discriminant: BallotEventD.TallyCompleted;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventTallyCompleted(
+  _out: AbiOutput,
+  _value: BallotEventTallyCompleted
+): void {
+  const { timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventStatusChanged {
+  discriminant: BallotEventD.StatusChanged;
+  status: BallotStatus;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventStatusChanged(
+  _out: AbiOutput,
+  _value: BallotEventStatusChanged
+): void {
+  const { status, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  serializeBallotStatus(_out, status);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventCommitteeDrawn {
+  discriminant: BallotEventD.CommitteeDrawn;
+  selected: BlockchainAddress[];
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventCommitteeDrawn(
+  _out: AbiOutput,
+  _value: BallotEventCommitteeDrawn
+): void {
+  const { selected, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(selected.length);
+  for (const selected_vec of selected) {
+    _out.writeAddress(selected_vec);
+  }
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventRunoffRequired {
+  discriminant: BallotEventD.RunoffRequired;
+  options: string[];
+  voterRoot: Hash;
+  voterCount: number;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventRunoffRequired(
+  _out: AbiOutput,
+  _value: BallotEventRunoffRequired
+): void {
+  const { options, voterRoot, voterCount, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(options.length);
+  for (const options_vec of options) {
+    _out.writeString(options_vec);
+  }
+  _out.writeHash(voterRoot);
+  _out.writeU32(voterCount);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventBudgetAllocated {
+  discriminant: BallotEventD.BudgetAllocated;
+  funded: string[];
+  spent: number;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventBudgetAllocated(
+  _out: AbiOutput,
+  _value: BallotEventBudgetAllocated
+): void {
+  const { funded, spent, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeI32(funded.length);
+  for (const funded_vec of funded) {
+    _out.writeString(funded_vec);
+  }
+  _out.writeU32(spent);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
+export interface BallotEventVoteRejected {
+  discriminant: BallotEventD.VoteRejected;
+  voter: BlockchainAddress;
+  timestamp: BN;
+  processId: string;
+}
+function serializeBallotEventVoteRejected(
+  _out: AbiOutput,
+  _value: BallotEventVoteRejected
+): void {
+  const { voter, timestamp, processId } = _value;
+  _out.writeU8(_value.discriminant);
+  _out.writeAddress(voter);
+  _out.writeU64(timestamp);
+  _out.writeString(processId);
+}
+
 export function initialize(
   name: string,
   description: string,
@@ -682,7 +1946,17 @@ export function deployBallot(
   title: string,
   description: string,
   administrator: BlockchainAddress,
-  durationSeconds: BN
+  durationSeconds: BN,
+  membershipPolicy: MembershipPolicy,
+  attachments: Attachment[],
+  eligibility: Eligibility,
+  resultDisclosure: ResultDisclosure,
+  participationMode: ParticipationMode,
+  ballotMode: BallotMode,
+  runoffRule: RunoffRule,
+  roles: BallotRoles,
+  approvals: ApprovalSettings,
+  minTenureDays: number
 ): Buffer {
   return AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeBytes(Buffer.from("07", "hex"));
@@ -694,6 +1968,19 @@ export function deployBallot(
     _out.writeString(description);
     _out.writeAddress(administrator);
     _out.writeU64(durationSeconds);
+    serializeMembershipPolicy(_out, membershipPolicy);
+    _out.writeI32(attachments.length);
+    for (const attachments_vec of attachments) {
+      serializeAttachment(_out, attachments_vec);
+    }
+    serializeEligibility(_out, eligibility);
+    serializeResultDisclosure(_out, resultDisclosure);
+    serializeParticipationMode(_out, participationMode);
+    serializeBallotMode(_out, ballotMode);
+    serializeRunoffRule(_out, runoffRule);
+    serializeBallotRoles(_out, roles);
+    serializeApprovalSettings(_out, approvals);
+    _out.writeU32(minTenureDays);
   });
 }
 
@@ -739,6 +2026,30 @@ export function updateMetadata(
   });
 }
 
+export function pruneProcesses(maxAgeSeconds: BN): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("09", "hex"));
+    _out.writeU64(maxAgeSeconds);
+  });
+}
+
+export function setUpgrader(upgrader: Option<BlockchainAddress>): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("0a", "hex"));
+    _out.writeBoolean(upgrader !== undefined);
+    if (upgrader !== undefined) {
+      _out.writeAddress(upgrader);
+    }
+  });
+}
+
+export function syncBallotVoters(ballot: BlockchainAddress): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("0b", "hex"));
+    _out.writeAddress(ballot);
+  });
+}
+
 export function handleBallotDeployedEvent(event: OrganizationEvent): Buffer {
   return AbiByteOutput.serializeBigEndian((_out) => {
     _out.writeBytes(Buffer.from("40", "hex"));
@@ -746,6 +2057,13 @@ export function handleBallotDeployedEvent(event: OrganizationEvent): Buffer {
   });
 }
 
+export function handleBallotEvent(event: BallotEvent): Buffer {
+  return AbiByteOutput.serializeBigEndian((_out) => {
+    _out.writeBytes(Buffer.from("41", "hex"));
+    serializeBallotEvent(_out, event);
+  });
+}
+
 export function deserializeState(state: StateWithClient): OrganizationState;
 export function deserializeState(bytes: Buffer): OrganizationState;
 export function deserializeState(
@@ -782,7 +2100,11 @@ export type Action =
   | AddMembersAction
   | DeployBallotAction
   | UpdateMetadataAction
-  | HandleBallotDeployedEventAction;
+  | PruneProcessesAction
+  | SetUpgraderAction
+  | SyncBallotVotersAction
+  | HandleBallotDeployedEventAction
+  | HandleBallotEventAction;
 
 export interface AddAdministratorAction {
   discriminant: "add_administrator";
@@ -815,6 +2137,16 @@ export interface DeployBallotAction {
   description: string;
   administrator: BlockchainAddress;
   durationSeconds: BN;
+  membershipPolicy: MembershipPolicy;
+  attachments: Attachment[];
+  eligibility: Eligibility;
+  resultDisclosure: ResultDisclosure;
+  participationMode: ParticipationMode;
+  ballotMode: BallotMode;
+  runoffRule: RunoffRule;
+  roles: BallotRoles;
+  approvals: ApprovalSettings;
+  minTenureDays: number;
 }
 export interface UpdateMetadataAction {
   discriminant: "update_metadata";
@@ -826,10 +2158,26 @@ export interface UpdateMetadataAction {
   xAccount: Option<string>;
   discordServer: Option<string>;
 }
+export interface PruneProcessesAction {
+  discriminant: "prune_processes";
+  maxAgeSeconds: BN;
+}
+export interface SetUpgraderAction {
+  discriminant: "set_upgrader";
+  upgrader: Option<BlockchainAddress>;
+}
+export interface SyncBallotVotersAction {
+  discriminant: "sync_ballot_voters";
+  ballot: BlockchainAddress;
+}
 export interface HandleBallotDeployedEventAction {
   discriminant: "handle_ballot_deployed_event";
   event: OrganizationEvent;
 }
+export interface HandleBallotEventAction {
+  discriminant: "handle_ballot_event";
+  event: BallotEvent;
+}
 export function deserializeAction(bytes: Buffer): Action {
   const input = AbiByteInput.createBigEndian(bytes);
   const shortname = input.readShortnameString();
@@ -850,13 +2198,23 @@ export function deserializeAction(bytes: Buffer): Action {
     return contract.deserializeDeployBallotAction(input);
   } else if (shortname === "08") {
     return contract.deserializeUpdateMetadataAction(input);
+  } else if (shortname === "09") {
+    return contract.deserializePruneProcessesAction(input);
+  } else if (shortname === "0a") {
+    return contract.deserializeSetUpgraderAction(input);
+  } else if (shortname === "0b") {
+    return contract.deserializeSyncBallotVotersAction(input);
   } else if (shortname === "40") {
     return contract.deserializeHandleBallotDeployedEventAction(input);
+  } else if (shortname === "41") {
+    return contract.deserializeHandleBallotEventAction(input);
   }
   throw new Error("Illegal shortname: " + shortname);
 }
 
-export type Callback = DeployBallotCallbackCallback;
+export type Callback =
+  | DeployBallotCallbackCallback
+  | TokenProbeCallbackCallback;
 
 export interface DeployBallotCallbackCallback {
   discriminant: "deploy_ballot_callback";
@@ -864,12 +2222,20 @@ export interface DeployBallotCallbackCallback {
   ballotTitle: string;
   processId: string;
 }
+export interface TokenProbeCallbackCallback {
+  discriminant: "token_probe_callback";
+  ballotContractAddress: BlockchainAddress;
+  ballotTitle: string;
+  initData: Buffer;
+}
 export function deserializeCallback(bytes: Buffer): Callback {
   const input = AbiByteInput.createBigEndian(bytes);
   const shortname = input.readShortnameString();
   const contract = new OrganizationGenerated(undefined, undefined);
   if (shortname === "20") {
     return contract.deserializeDeployBallotCallbackCallback(input);
+  } else if (shortname === "21") {
+    return contract.deserializeTokenProbeCallbackCallback(input);
   }
   throw new Error("Illegal shortname: " + shortname);
 }
@@ -6,11 +6,9 @@ import {
   computeTally,
   cancelBallot,
   syncVoters,
+  castVote,
 } from "@/contracts/BallotGenerated";
-import {
-  BlockchainAddress,
-  AbiBitOutput,
-} from "@partisiablockchain/abi-client";
+import { Hash } from "@partisiablockchain/abi-client";
 import { useMutation, useQueryClient } from "@tanstack/react-query";
 import { useMemo } from "react";
 import { useTransaction } from "./useTransaction";
@@ -67,21 +65,26 @@ export function useBallotContract() {
     mutationFn: async ({
       ballotAddress,
       choice,
+      voterIndex,
+      membershipProof,
     }: {
       ballotAddress: string;
       choice: number;
+      voterIndex: number;
+      membershipProof: Hash[];
     }) => {
       if (!account) throw new Error("Wallet not connected");
 
-      const secretInputData = AbiBitOutput.serialize((_out) => {
-        _out.writeI8(choice);
-      });
+      const { secretInput, publicRpc } = castVote(
+        voterIndex,
+        membershipProof
+      ).secretInput(choice);
 
       return sendTransaction({
         type: "secret",
         address: ballotAddress,
-        secretInput: secretInputData,
-        publicRpc: Buffer.from("60", "hex"),
+        secretInput,
+        publicRpc,
         gasCost: 100_000,
       });
     },
@@ -125,13 +128,15 @@ export function useBallotContract() {
   const syncVotersMutation = useMutation({
     mutationFn: async ({
       ballotAddress,
-      voters,
+      voterRoot,
+      voterCount,
     }: {
       ballotAddress: string;
-      voters: BlockchainAddress[];
+      voterRoot: Hash;
+      voterCount: number;
     }) => {
       if (!account) throw new Error("Wallet not connected");
-      const rpc = syncVoters(voters);
+      const rpc = syncVoters(voterRoot, voterCount);
       return sendTransaction({
         type: "regular",
         address: ballotAddress,
@@ -147,14 +152,32 @@ export function useBallotContract() {
   return useMemo(
     () => ({
       getState: getBallotState,
-      castVote: (ballotAddress: string, choice: number) =>
-        castVoteMutation.mutateAsync({ ballotAddress, choice }),
+      castVote: (
+        ballotAddress: string,
+        choice: number,
+        voterIndex: number,
+        membershipProof: Hash[]
+      ) =>
+        castVoteMutation.mutateAsync({
+          ballotAddress,
+          choice,
+          voterIndex,
+          membershipProof,
+        }),
       computeTally: (ballotAddress: string) =>
         computeTallyMutation.mutateAsync(ballotAddress),
       cancelBallot: (ballotAddress: string) =>
         cancelBallotMutation.mutateAsync(ballotAddress),
-      syncVoters: (ballotAddress: string, voters: BlockchainAddress[]) =>
-        syncVotersMutation.mutateAsync({ ballotAddress, voters }),
+      syncVoters: (
+        ballotAddress: string,
+        voterRoot: Hash,
+        voterCount: number
+      ) =>
+        syncVotersMutation.mutateAsync({
+          ballotAddress,
+          voterRoot,
+          voterCount,
+        }),
     }),
     [
       castVoteMutation,
@@ -175,11 +198,20 @@ export function useCastVote() {
     mutationFn: async ({
       ballotAddress,
       choice,
+      voterIndex,
+      membershipProof,
     }: {
       ballotAddress: string;
       choice: number;
+      voterIndex: number;
+      membershipProof: Hash[];
     }) => {
-      const txn = await ballotContract.castVote(ballotAddress, choice);
+      const txn = await ballotContract.castVote(
+        ballotAddress,
+        choice,
+        voterIndex,
+        membershipProof
+      );
       return txn;
     },
     onSuccess: (_, variables) => {
@@ -238,12 +270,18 @@ export function useSyncVoters() {
   return useMutation({
     mutationFn: async ({
       ballotAddress,
-      voters,
+      voterRoot,
+      voterCount,
     }: {
       ballotAddress: string;
-      voters: BlockchainAddress[];
+      voterRoot: Hash;
+      voterCount: number;
     }) => {
-      const txn = await ballotContract.syncVoters(ballotAddress, voters);
+      const txn = await ballotContract.syncVoters(
+        ballotAddress,
+        voterRoot,
+        voterCount
+      );
       return txn;
     },
     onSuccess: (_, variables) => {
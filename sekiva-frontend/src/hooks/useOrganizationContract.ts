@@ -9,6 +9,12 @@ import {
   addMember,
   removeMember,
   addMembers,
+  BallotModeD,
+  EligibilityD,
+  MembershipPolicyD,
+  ParticipationModeD,
+  ResultDisclosureD,
+  RunoffRuleD,
 } from "@/contracts/OrganizationGenerated";
 import { BlockchainAddress, BN } from "@partisiablockchain/abi-client";
 import {
@@ -247,7 +253,17 @@ export function useDeployBallot() {
           params.ballotInfo.title,
           params.ballotInfo.description,
           params.ballotInfo.administrator,
-          params.ballotInfo.durationSeconds
+          params.ballotInfo.durationSeconds,
+          { discriminant: MembershipPolicyD.Snapshot },
+          [],
+          { discriminant: EligibilityD.Membership },
+          { discriminant: ResultDisclosureD.Public },
+          { discriminant: ParticipationModeD.Public },
+          { discriminant: BallotModeD.Vote },
+          { discriminant: RunoffRuleD.None },
+          { talliers: [], cancellers: [], observers: [] },
+          { threshold: 0, windowSeconds: new BN(0) },
+          0
         );
 
         return await sendTransaction({
@@ -4,6 +4,7 @@ import {
   deserializeState,
 } from "@/contracts/BallotGenerated";
 import { ContractError } from "@/types/contract";
+import { Hash } from "@partisiablockchain/abi-client";
 import {
   BlockchainTransactionClient,
   SentTransaction,
@@ -60,7 +61,11 @@ export const getBallotState = async (id: BallotId): Promise<Ballot> => {
 
 export interface BallotClient {
   getState: () => Promise<Ballot>;
-  castVote: (choice: number) => Promise<SentTransaction>;
+  castVote: (
+    choice: number,
+    voterIndex: number,
+    membershipProof: Hash[]
+  ) => Promise<SentTransaction>;
 }
 
 export const createBallotClient = (
@@ -70,9 +75,13 @@ export const createBallotClient = (
   walletAddress: string
 ): BallotClient => ({
   getState: () => getBallotState(contractAddress),
-  castVote: async (choice: number): Promise<SentTransaction> => {
+  castVote: async (
+    choice: number,
+    voterIndex: number,
+    membershipProof: Hash[]
+  ): Promise<SentTransaction> => {
     try {
-      const castVoteSecretInputBuilder = castVote();
+      const castVoteSecretInputBuilder = castVote(voterIndex, membershipProof);
       const secretInput = castVoteSecretInputBuilder.secretInput(choice);
       const transaction = await zkClient.buildOnChainInputTransaction(
         walletAddress,
@@ -0,0 +1,145 @@
+import { BallotState } from "@/contracts/BallotGenerated";
+import { OrganizationState } from "@/contracts/OrganizationGenerated";
+import { BlockchainAddress, Hash } from "@partisiablockchain/abi-client";
+import { sha256 } from "hash.js";
+
+// Must stay in sync with the voter Merkle tree of the organization and ballot contracts.
+
+const digest = (bytes: number[]): Buffer =>
+  Buffer.from(sha256().update(bytes).digest());
+
+const voterLeaf = (voter: BlockchainAddress, index: number): Buffer => {
+  const position = Buffer.alloc(4);
+  position.writeUInt32BE(index);
+  return digest([0x00, ...position, ...voter.asBuffer()]);
+};
+
+const voterNode = (left: Buffer, right: Buffer): Buffer => {
+  const [first, second] =
+    Buffer.compare(left, right) <= 0 ? [left, right] : [right, left];
+  return digest([0x01, ...first, ...second]);
+};
+
+const compareVoters = (a: BlockchainAddress, b: BlockchainAddress): number =>
+  Buffer.compare(a.asBuffer(), b.asBuffer());
+
+/**
+ * Computes the root of the voter Merkle tree.
+ *
+ * @param voters the voters, in leaf order.
+ * @return the root, or undefined for an empty tree.
+ */
+export const voterMerkleRoot = (
+  voters: BlockchainAddress[]
+): Buffer | undefined => {
+  let level = voters.map((voter, index) => voterLeaf(voter, index));
+  while (level.length > 1) {
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(
+        i + 1 < level.length ? voterNode(level[i], level[i + 1]) : level[i]
+      );
+    }
+    level = next;
+  }
+  return level[0];
+};
+
+export interface VoterProof {
+  voterIndex: number;
+  membershipProof: Hash[];
+}
+
+/**
+ * Computes the membership proof of a voter in a ballot's voter snapshot.
+ *
+ * @param voters the snapshot, in leaf order.
+ * @param voter the voter to prove membership of.
+ * @return the voter's position in the snapshot and the sibling hashes from the voter's leaf up
+ * to the root.
+ */
+export const voterMerkleProof = (
+  voters: BlockchainAddress[],
+  voter: BlockchainAddress
+): VoterProof => {
+  const voterIndex = voters.findIndex(
+    (v) => v.asString() === voter.asString()
+  );
+  if (voterIndex < 0) {
+    throw new Error("Voter is not part of the ballot's voter snapshot");
+  }
+
+  let index = voterIndex;
+  const proof: Hash[] = [];
+  let level = voters.map((voter, index) => voterLeaf(voter, index));
+  while (level.length > 1) {
+    const sibling = index ^ 1;
+    // An unpaired node is carried up unchanged and adds nothing to the proof
+    if (sibling < level.length) {
+      proof.push(Hash.fromString(level[sibling].toString("hex")));
+    }
+
+    const next: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      next.push(
+        i + 1 < level.length ? voterNode(level[i], level[i + 1]) : level[i]
+      );
+    }
+    level = next;
+    index = Math.floor(index / 2);
+  }
+  return { voterIndex, membershipProof: proof };
+};
+
+/**
+ * Rebuilds the voter snapshot of a ballot from the current state.
+ *
+ * The organization only sends the root and size of the snapshot, its members at the time
+ * ordered by address. Those that are still members joined before anyone who joined later, so
+ * the snapshot is the earliest joined members, together with the members the ballot recorded
+ * as removed since. Each candidate snapshot is checked against the ballot's voter root.
+ *
+ * @param organization the state of the organization that deployed the ballot.
+ * @param ballot the state of the ballot.
+ * @return the snapshot in leaf order, or undefined if it cannot be rebuilt, for example
+ * because a member left without the ballot recording it. The administrator then has to
+ * provide it.
+ */
+export const ballotVoterSnapshot = (
+  organization: OrganizationState,
+  ballot: BallotState
+): BlockchainAddress[] | undefined => {
+  const joinedAt = (member: BlockchainAddress): bigint | undefined => {
+    for (const [address, since] of organization.memberSince) {
+      if (address.asString() === member.asString()) {
+        return BigInt(since.toString());
+      }
+    }
+    return undefined;
+  };
+  // A member without a join time is never part of a snapshot
+  const byJoinTime = organization.members
+    .map((member) => ({ member, since: joinedAt(member) }))
+    .filter(
+      (entry): entry is { member: BlockchainAddress; since: bigint } =>
+        entry.since !== undefined
+    )
+    .sort((a, b) => (a.since < b.since ? -1 : a.since > b.since ? 1 : 0))
+    .map(({ member }) => member);
+  const expectedRoot = ballot.voterRoot.asString();
+
+  // Members removed since the snapshot are part of it, unless they joined after it
+  for (const removed of [ballot.removedVoters, []]) {
+    const remaining = ballot.voterCount - removed.length;
+    if (remaining < 0 || remaining > byJoinTime.length) {
+      continue;
+    }
+    const voters = [...byJoinTime.slice(0, remaining), ...removed].sort(
+      compareVoters
+    );
+    if (voterMerkleRoot(voters)?.toString("hex") === expectedRoot) {
+      return voters;
+    }
+  }
+  return undefined;
+};
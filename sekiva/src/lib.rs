@@ -10,9 +10,11 @@ use pbc_contract_common::address::{Address, AddressType, Shortname};
 use pbc_contract_common::context::{CallbackContext, ContractContext};
 use pbc_contract_common::events::EventGroup;
 use pbc_contract_common::sorted_vec_map::{SortedVecMap, SortedVecSet};
+use pbc_contract_common::upgrade::ContractHashes;
 use pbc_traits::WriteRPC;
 use read_write_rpc_derive::ReadWriteRPC;
 use read_write_state_derive::ReadWriteState;
+use sekiva_common::{require, ContractError, ProcessCounter};
 
 // https://browser.testnet.partisiablockchain.com/contracts/0197a0e238e924025bad144aa0c4913e46308f9a4d/deployContractWithBinderId
 const DEPLOY_PUBLIC_CONTRACT_ADDRESS: Address = Address {
@@ -23,6 +25,9 @@ const DEPLOY_PUBLIC_CONTRACT_ADDRESS: Address = Address {
     ],
 };
 
+/// Version of the `SekivaFactoryState` layout, bumped whenever the layout changes
+const STATE_VERSION: u32 = 1;
+
 const DEPLOY_SHORTNAME: Shortname = Shortname::from_u32(4);
 const WASM_BINDER_ID: i32 = 9;
 const ORGANIZATION_DEPLOYED_SHORTNAME: Shortname = Shortname::from_u32(0x45);
@@ -40,6 +45,35 @@ enum OrganizationProcessState {
     Deleted {},
 }
 
+impl OrganizationProcessState {
+    /// Whether the process has ended, so its record may be pruned
+    fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            OrganizationProcessState::Active {} | OrganizationProcessState::Deleted {}
+        )
+    }
+}
+
+/// Tracking record of a single process
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct OrganizationProcessRecord {
+    state: OrganizationProcessState,
+    timestamp: u64,
+    failure_reason: Option<String>, // Why the process failed
+}
+
+/// Bounded log of processes.
+///
+/// Finished records are pruned once the log exceeds its maximum size, or on request by age.
+/// Pruned records are only kept as counts.
+#[derive(CreateTypeSpec, ReadWriteState, Clone)]
+struct OrganizationProcessLog {
+    records: SortedVecMap<String, OrganizationProcessRecord>,
+    pruned_count: u64,    // Number of records pruned so far
+    pruned_failures: u64, // Number of pruned records that had a failure reason
+}
+
 #[derive(CreateTypeSpec, ReadWriteState, Clone)]
 enum ContractType {
     #[discriminant(0)]
@@ -93,6 +127,7 @@ enum OrganizationEvent {
 
 #[state]
 pub struct SekivaFactoryState {
+    state_version: u32, // Layout version of this state, see `STATE_VERSION`
     admin: Address,
     organizations: SortedVecSet<Address>,
     ballots: SortedVecSet<Address>,
@@ -102,16 +137,90 @@ pub struct SekivaFactoryState {
     organization_contract_wasm: Vec<u8>,
     organization_contract_abi: Vec<u8>,
     event_nonce: u64,
-    organization_processes: SortedVecMap<String, OrganizationProcessState>,
+    organization_processes: OrganizationProcessLog,
+    process_counter: ProcessCounter, // Allocates process IDs
+    upgrader: Option<Address>,       // Who may upgrade the contract, `None` disables upgrades
 }
 
-/// Generates a unique process ID using timestamp and transaction bytes
-fn generate_process_id(ctx: &ContractContext) -> String {
-    let bytes_hex = ctx.original_transaction.bytes[0..8]
-        .iter()
-        .map(|b| format!("{:02x}", b))
-        .collect::<String>();
-    format!("{}-{}", ctx.block_time, bytes_hex)
+/// Maximum number of process records kept before the oldest finished ones are pruned.
+const MAX_TRACKED_PROCESSES: usize = 100;
+
+impl OrganizationProcessLog {
+    fn new() -> Self {
+        OrganizationProcessLog {
+            records: SortedVecMap::new(),
+            pruned_count: 0,
+            pruned_failures: 0,
+        }
+    }
+
+    /// Records the state of a process
+    fn record(&mut self, process_id: String, state: OrganizationProcessState, timestamp: u64) {
+        self.insert(
+            process_id,
+            OrganizationProcessRecord {
+                state,
+                timestamp,
+                failure_reason: None,
+            },
+        );
+    }
+
+    /// Records the state of a process together with why it failed
+    fn record_failure(
+        &mut self,
+        process_id: String,
+        state: OrganizationProcessState,
+        timestamp: u64,
+        reason: String,
+    ) {
+        self.insert(
+            process_id,
+            OrganizationProcessRecord {
+                state,
+                timestamp,
+                failure_reason: Some(reason),
+            },
+        );
+    }
+
+    fn insert(&mut self, process_id: String, record: OrganizationProcessRecord) {
+        self.records.insert(process_id, record);
+        while self.records.len() > MAX_TRACKED_PROCESSES {
+            let oldest = self
+                .records
+                .iter()
+                .filter(|(_, record)| record.state.is_finished())
+                .min_by_key(|(_, record)| record.timestamp)
+                .map(|(process_id, _)| process_id.clone());
+            match oldest {
+                Some(process_id) => self.archive(process_id),
+                None => break,
+            }
+        }
+    }
+
+    /// Prunes all finished records older than the cutoff
+    fn prune_older_than(&mut self, cutoff: u64) {
+        let expired: Vec<String> = self
+            .records
+            .iter()
+            .filter(|(_, record)| record.state.is_finished() && record.timestamp < cutoff)
+            .map(|(process_id, _)| process_id.clone())
+            .collect();
+        for process_id in expired {
+            self.archive(process_id);
+        }
+    }
+
+    fn archive(&mut self, process_id: String) {
+        if let Some(record) = self.records.remove(&process_id) {
+            self.pruned_count += 1;
+            if record.failure_reason.is_some() {
+                self.pruned_failures += 1;
+            }
+        }
+    }
 }
 
 /// Initial function to create the initial state.
@@ -135,6 +244,7 @@ pub fn initialize(
     organization_contract_abi: Vec<u8>,
 ) -> (SekivaFactoryState, Vec<EventGroup>) {
     let state = SekivaFactoryState {
+        state_version: STATE_VERSION,
         admin: ctx.sender,
         organizations: SortedVecSet::new(),
         ballots: SortedVecSet::new(),
@@ -144,7 +254,9 @@ pub fn initialize(
         organization_contract_wasm,
         organization_contract_abi,
         event_nonce: 0,
-        organization_processes: SortedVecMap::new(),
+        organization_processes: OrganizationProcessLog::new(),
+        process_counter: ProcessCounter::new(),
+        upgrader: Some(ctx.sender),
     };
 
     (state, vec![])
@@ -187,12 +299,17 @@ fn deploy_organization(
         identifier: ctx.original_transaction.bytes[12..32].try_into().unwrap(),
     };
 
-    // Generate a process ID for this deployment
-    let process_id = generate_process_id(&ctx);
+    // Allocate a process ID for this deployment
+    let mut process_counter = state.process_counter.clone();
+    let process_id = process_counter.next_id(ctx.contract_address);
 
     // Track the process
     let mut organization_processes = state.organization_processes.clone();
-    organization_processes.insert(process_id.clone(), OrganizationProcessState::Created {});
+    organization_processes.record(
+        process_id.clone(),
+        OrganizationProcessState::Created {},
+        ctx.block_production_time as u64,
+    );
 
     // Increment event nonce
     let event_nonce = state.event_nonce + 1;
@@ -229,6 +346,7 @@ fn deploy_organization(
     (
         SekivaFactoryState {
             organization_processes,
+            process_counter,
             event_nonce,
             ..state
         },
@@ -263,8 +381,8 @@ fn deploy_organization_callback(
     let event_nonce = state.event_nonce + 1;
 
     // Check if we already processed this callback (handles potential replays)
-    if let Some(existing_state) = organization_processes.get(&process_id) {
-        match existing_state {
+    if let Some(existing) = organization_processes.records.get(&process_id) {
+        match existing.state {
             OrganizationProcessState::Created {} => {} // Continue processing
             _ => {
                 // This callback has already been processed, return current state with incremented nonce
@@ -281,7 +399,11 @@ fn deploy_organization_callback(
 
     if callback_ctx.success {
         // Mark as deployed
-        organization_processes.insert(process_id.clone(), OrganizationProcessState::Deployed {});
+        organization_processes.record(
+            process_id.clone(),
+            OrganizationProcessState::Deployed {},
+            ctx.block_production_time as u64,
+        );
 
         // Add to organizations
         let mut organizations = state.organizations.clone();
@@ -320,7 +442,12 @@ fn deploy_organization_callback(
         )
     } else {
         // Mark as failed with appropriate error state
-        organization_processes.insert(process_id.clone(), OrganizationProcessState::Deleted {});
+        organization_processes.record_failure(
+            process_id.clone(),
+            OrganizationProcessState::Deleted {},
+            ctx.block_production_time as u64,
+            "Deployment callback failed".to_string(),
+        );
 
         // Return updated state with incremented nonce, could emit failure event here
         (
@@ -414,10 +541,16 @@ fn handle_organization_event(
 
             // Update organization process state if needed
             let mut organization_processes = state.organization_processes.clone();
-            if let Some(OrganizationProcessState::Deployed {}) =
-                organization_processes.get(&process_id)
+            if let Some(OrganizationProcessRecord {
+                state: OrganizationProcessState::Deployed {},
+                ..
+            }) = organization_processes.records.get(&process_id)
             {
-                organization_processes.insert(process_id, OrganizationProcessState::Active {});
+                organization_processes.record(
+                    process_id,
+                    OrganizationProcessState::Active {},
+                    ctx.block_production_time as u64,
+                );
             }
 
             (
@@ -487,7 +620,11 @@ fn handle_organization_event(
         } => {
             // Update organization process state to active
             let mut organization_processes = state.organization_processes.clone();
-            organization_processes.insert(process_id, OrganizationProcessState::Active {});
+            organization_processes.record(
+                process_id,
+                OrganizationProcessState::Active {},
+                ctx.block_production_time as u64,
+            );
 
             (
                 SekivaFactoryState {
@@ -523,7 +660,11 @@ fn handle_organization_deployed_event(
         } => {
             // Update organization process state to active
             let mut organization_processes = state.organization_processes.clone();
-            organization_processes.insert(process_id, OrganizationProcessState::Active {});
+            organization_processes.record(
+                process_id,
+                OrganizationProcessState::Active {},
+                ctx.block_production_time as u64,
+            );
 
             (
                 SekivaFactoryState {
@@ -536,3 +677,81 @@ fn handle_organization_deployed_event(
         _ => (state, vec![]),
     }
 }
+
+/// Prunes finished organization process records older than the given age.
+/// Only the factory admin can prune.
+#[action(shortname = 0x02)]
+fn prune_processes(
+    ctx: ContractContext,
+    state: SekivaFactoryState,
+    max_age_seconds: u64,
+) -> SekivaFactoryState {
+    require!(
+        ctx.sender == state.admin,
+        ContractError::OnlyFactoryAdmin,
+        "Only the factory admin can prune processes"
+    );
+
+    let cutoff = (ctx.block_production_time as u64).saturating_sub(max_age_seconds * 1000);
+    let mut organization_processes = state.organization_processes.clone();
+    organization_processes.prune_older_than(cutoff);
+
+    SekivaFactoryState {
+        organization_processes,
+        ..state
+    }
+}
+
+/// Sets who may upgrade the factory contract. `None` disables upgrades.
+/// Only the factory admin can set the upgrader.
+#[action(shortname = 0x03)]
+fn set_upgrader(
+    ctx: ContractContext,
+    state: SekivaFactoryState,
+    upgrader: Option<Address>,
+) -> SekivaFactoryState {
+    require!(
+        ctx.sender == state.admin,
+        ContractError::OnlyFactoryAdmin,
+        "Only the factory admin can set the upgrader"
+    );
+
+    SekivaFactoryState { upgrader, ..state }
+}
+
+/// Decides whether the contract may be upgraded.
+///
+/// Only the upgrader set by the factory admin can upgrade the factory.
+#[upgrade_is_allowed]
+fn is_upgrade_allowed(
+    ctx: ContractContext,
+    state: SekivaFactoryState,
+    _old_contract_hashes: ContractHashes,
+    _new_contract_hashes: ContractHashes,
+    _new_contract_rpc: Vec<u8>,
+) -> bool {
+    state.upgrader == Some(ctx.sender)
+}
+
+/// Migrates the state of the previous contract version to the current layout.
+///
+/// Version 1 is the first versioned layout, so there is nothing to migrate yet and a state of
+/// version 1 is read as `SekivaFactoryState` unchanged. Factories deployed before states were
+/// versioned have a different layout and cannot be upgraded. When the layout changes, bump
+/// `STATE_VERSION`, keep the replaced struct as `SekivaFactoryStateV<n>` and convert it field
+/// by field here.
+#[upgrade]
+fn upgrade(ctx: ContractContext, state: SekivaFactoryState) -> SekivaFactoryState {
+    require!(
+        state.state_version <= STATE_VERSION,
+        ContractError::UnsupportedStateVersion,
+        "Cannot migrate state version {} to {}",
+        state.state_version,
+        STATE_VERSION
+    );
+
+    SekivaFactoryState {
+        state_version: STATE_VERSION,
+        ..state
+    }
+}